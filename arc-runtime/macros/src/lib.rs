@@ -14,6 +14,7 @@ mod proc_macro_attrs {
         pub mod nonpersistent_tasks;
         pub mod persistent_tasks;
         pub mod structs;
+        pub mod traits;
     }
 }
 mod proc_macros;
@@ -178,6 +179,46 @@ pub fn vector(input: TokenStream) -> TokenStream {
     proc_macros::vector(input)
 }
 
+/// Creates a function value which captures an environment. The code of the closure must be
+/// declared among the closures of `declare_functions!` and take the environment as its first
+/// argument, followed by the tuple of arguments and the context.
+///
+/// ```ignore
+/// fn add((n,): (i32,), (x,): (i32,), ctx: Context) -> i32 { n + x }
+/// declare_functions!(; closures: add);
+/// let f: function!((i32) -> i32) = closure!(add, (1,));
+/// ```
+#[proc_macro]
+pub fn closure(input: TokenStream) -> TokenStream {
+    proc_macros::closure(input)
+}
+
+/// Declares the canonical structs of records. Each record is a set of field names, and open
+/// records end with `..`.
+///
+/// ```ignore
+/// declare_records!({k, v}, {k, ..});
+/// ```
+///
+/// The structs are declared in a module named `records`, so the macro can only be invoked once
+/// per module.
+#[proc_macro]
+pub fn declare_records(input: TokenStream) -> TokenStream {
+    proc_macros::declare_records(input)
+}
+
+/// Names the type of a record, or creates a record value.
+///
+/// ```ignore
+/// let r: record!(k: i32, v: i32) = record!(k = 1, v = 2);
+/// let o: record!(k: i32, ..) = Open::open(r);
+/// let p: record!(k: i32, ..) = record!(k = 1, .. = rest);
+/// ```
+#[proc_macro]
+pub fn record(input: TokenStream) -> TokenStream {
+    proc_macros::record(input)
+}
+
 #[proc_macro]
 pub fn erase(input: TokenStream) -> TokenStream {
     proc_macros::erase(input)
@@ -246,7 +287,10 @@ pub fn rewrite(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
         syn::Item::Fn(item) => proc_macro_attrs::rewrite::functions::rewrite(attr, item),
         syn::Item::Impl(item) => proc_macro_attrs::rewrite::impls::rewrite(attr, item),
-        _ => panic!("#[rewrite] expects an enum, struct, function, impl, or module as input."),
+        syn::Item::Trait(item) => proc_macro_attrs::rewrite::traits::rewrite(attr, item),
+        _ => panic!(
+            "#[rewrite] expects an enum, struct, function, impl, trait, or module as input."
+        ),
     }
 }
 
@@ -254,6 +298,27 @@ pub(crate) fn new_id(s: impl ToString) -> syn::Ident {
     syn::Ident::new(&s.to_string(), pm2::Span::call_site())
 }
 
+/// Implements `Tagged` for the rewritten type `ty` named `id`, whose tag is the path of the
+/// type followed by the tags of its type arguments.
+pub(crate) fn tagged(
+    ty: pm2::TokenStream,
+    id: &syn::Ident,
+    generics: &syn::Generics,
+) -> pm2::TokenStream {
+    let param = generics.type_params().map(|p| &p.ident).collect::<Vec<_>>();
+    let (_, type_generics, where_clause) = generics.split_for_impl();
+    quote::quote!(
+        impl<#(#param: Sharable + Tagged),*> Tagged for #ty #type_generics #where_clause {
+            fn tag() -> std::string::String {
+                arc_runtime::data::erased::tag_of(
+                    concat!(module_path!(), "::", stringify!(#id)),
+                    &[#(<#param as Tagged>::tag()),*],
+                )
+            }
+        }
+    )
+}
+
 pub(crate) fn get_metas(attr: &[syn::NestedMeta]) -> Vec<syn::Meta> {
     attr.into_iter()
         .filter_map(|a| match a {
@@ -4,6 +4,7 @@ use proc_macro as pm;
 use quote::quote;
 
 use crate::new_id;
+use crate::tagged;
 
 #[allow(unused)]
 pub(crate) fn rewrite(_: syn::AttributeArgs, mut item: syn::ItemEnum) -> pm::TokenStream {
@@ -48,6 +49,11 @@ pub(crate) fn rewrite(_: syn::AttributeArgs, mut item: syn::ItemEnum) -> pm::Tok
     let concrete_id = new_id(format!("Concrete{}", abstract_id));
     let sharable_mod_id = new_id(format!("sharable_{}", abstract_id));
     let sendable_mod_id = new_id(format!("sendable_{}", abstract_id));
+    let tagged = tagged(
+        quote!(#sharable_mod_id::#abstract_id),
+        &abstract_id,
+        &item.generics,
+    );
 
     let mut concrete_sharable_item = item.clone();
     let mut concrete_sendable_item = item.clone();
@@ -99,6 +105,12 @@ pub(crate) fn rewrite(_: syn::AttributeArgs, mut item: syn::ItemEnum) -> pm::Tok
         .map(|v| &v.ident)
         .collect::<Vec<_>>();
 
+    let variant_ty = concrete_sharable_item
+        .variants
+        .iter()
+        .map(|v| &v.fields.iter().next().unwrap().ty)
+        .collect::<Vec<_>>();
+
     quote!(
 
         use arc_runtime::prelude::*;
@@ -120,7 +132,7 @@ pub(crate) fn rewrite(_: syn::AttributeArgs, mut item: syn::ItemEnum) -> pm::Tok
             #[derive(Clone, Debug, From, Abstract, Serialize, Deserialize)]
             #[serde(bound = "")]
             #[from(forward)]
-            pub struct #abstract_id #sendable_impl_generics(pub Box<#concrete_id #type_generics>) #where_clause;
+            pub struct #abstract_id #sendable_impl_generics(pub arc_runtime::data::graphs::Shared<#concrete_id #type_generics>) #where_clause;
  
             #[derive(Clone, Debug, Serialize, Deserialize)]
             #[serde(bound = "")]
@@ -131,28 +143,70 @@ pub(crate) fn rewrite(_: syn::AttributeArgs, mut item: syn::ItemEnum) -> pm::Tok
         use #sharable_mod_id::#concrete_id::*;
         use #sharable_mod_id::#concrete_id;
 
+        #tagged
+
         impl #sharable_impl_generics DynSharable for #sharable_mod_id::#abstract_id #type_generics #where_clause {
             type T = #sendable_mod_id::#abstract_id #into_generics;
             fn into_sendable(&self, ctx: Context) -> Self::T {
+                arc_runtime::data::graphs::into_sendable(self, ctx).into()
+            }
+            // The placeholder is the first variant whose payload has a placeholder.
+            fn hollow(ctx: Context) -> std::option::Option<Self> {
+                arc_runtime::data::graphs::hollow(|| {
+                    None
+                        #(.or_else(|| Some(#sharable_mod_id::#concrete_id::#variant_id(<#variant_ty as DynSharable>::hollow(ctx)?))))*
+                        .map(|concrete| concrete.alloc(ctx))
+                })
+            }
+        }
+
+        impl #sharable_impl_generics arc_runtime::data::graphs::Encode for #sharable_mod_id::#abstract_id #type_generics #where_clause {
+            type Node = #sendable_mod_id::#concrete_id #into_generics;
+            type Sendable = #sendable_mod_id::#abstract_id #into_generics;
+            fn key(&self) -> usize {
+                &*self.0 as *const _ as usize
+            }
+            fn encode(&self, ctx: Context) -> Self::Node {
                 match &*self.0 {
                     #(
                         #sharable_mod_id::#concrete_id::#variant_id(x) =>
-                        #sendable_mod_id::#concrete_id::#variant_id(x.clone().into_sendable(ctx)).into()
+                        #sendable_mod_id::#concrete_id::#variant_id(x.clone().into_sendable(ctx))
                     ),*
                 }
             }
         }
 
+        impl #sharable_impl_generics Interpolate for #sharable_mod_id::#abstract_id #type_generics #where_clause {}
+
         impl #sendable_impl_generics DynSendable for #sendable_mod_id::#abstract_id #type_generics #where_clause {
             type T = #sharable_mod_id::#abstract_id #into_generics;
             fn into_sharable(&self, ctx: Context) -> Self::T {
-                match &*self.0 {
+                arc_runtime::data::graphs::into_sharable(self, ctx)
+            }
+        }
+
+        impl #sendable_impl_generics arc_runtime::data::graphs::Decode for #sendable_mod_id::#abstract_id #type_generics #where_clause {
+            type Node = #sendable_mod_id::#concrete_id #type_generics;
+            type Concrete = #sharable_mod_id::#concrete_id #into_generics;
+            fn shared(&self) -> &arc_runtime::data::graphs::Shared<Self::Node> {
+                &self.0
+            }
+            fn decode(node: &Self::Node, ctx: Context) -> Self::Concrete {
+                match node {
                     #(
                         #sendable_mod_id::#concrete_id::#variant_id(x) =>
-                        #sharable_mod_id::#concrete_id::#variant_id(x.into_sharable(ctx)).alloc(ctx)
+                        #sharable_mod_id::#concrete_id::#variant_id(x.into_sharable(ctx))
                     ),*
                 }
             }
+            fn alloc(concrete: Self::Concrete, ctx: Context) -> Self::T {
+                concrete.alloc(ctx)
+            }
+            fn fill(node: &Self::T, concrete: Self::Concrete, ctx: Context) {
+                let mut gc = node.0.clone();
+                *gc = concrete;
+                gc.write_barrier(ctx);
+            }
         }
 
     )
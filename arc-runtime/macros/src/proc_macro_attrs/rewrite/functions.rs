@@ -4,6 +4,8 @@ use proc_macro2 as pm2;
 // use quote::quote;
 use quote::quote;
 use std::collections::HashMap;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
 use syn::visit_mut::VisitMut;
 
 pub(crate) fn rewrite(_attr: syn::AttributeArgs, mut item: syn::ItemFn) -> pm::TokenStream {
@@ -124,7 +126,10 @@ impl VisitMut for Visitor {
 
     fn visit_expr_macro_mut(&mut self, i: &mut syn::ExprMacro) {
         syn::visit_mut::visit_expr_macro_mut(self, i);
-        i.mac.tokens = self.visit_token_stream(i.mac.tokens.clone());
+        i.mac.tokens = match self.visit_assertion(&i.mac) {
+            Some(tokens) => tokens,
+            None => self.visit_token_stream(i.mac.tokens.clone()),
+        };
     }
 
     // Visit expr before pattern
@@ -160,7 +165,8 @@ impl VisitMut for Visitor {
 fn is_primitive(t: &syn::Type) -> bool {
     match t {
         syn::Type::Path(p) => [
-            "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64", "unit",
+            "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64", "unit", "char",
+            "DateTime", "Duration",
         ]
         .contains(&p.path.segments.last().unwrap().ident.to_string().as_str()),
         syn::Type::Reference(tr) => match &*tr.elem {
@@ -181,7 +187,28 @@ fn get_path_ident(p: &syn::Path) -> Option<syn::Ident> {
     }
 }
 
+/// Macros whose arguments are expressions which are rewritten like any other expression.
+const ASSERTIONS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+];
+
 impl Visitor {
+    /// Rewrites the arguments of an assertion, so that calls in them also get a context.
+    fn visit_assertion(&mut self, mac: &syn::Macro) -> Option<pm2::TokenStream> {
+        if !ASSERTIONS.iter().any(|name| mac.path.is_ident(name)) {
+            return None;
+        }
+        let parser = Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated;
+        let mut args = parser.parse2(mac.tokens.clone()).ok()?;
+        args.iter_mut().for_each(|arg| self.visit_expr_mut(arg));
+        Some(quote!(#args))
+    }
+
     fn visit_token_stream(&mut self, tokens: pm2::TokenStream) -> pm2::TokenStream {
         let mut result = pm2::TokenStream::new();
         for token in tokens {
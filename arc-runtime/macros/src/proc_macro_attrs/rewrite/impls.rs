@@ -1,42 +1,50 @@
 use proc_macro as pm;
+use proc_macro2 as pm2;
 use quote::quote;
+use syn::visit_mut::VisitMut;
 
-pub(crate) fn rewrite(_attr: syn::AttributeArgs, item: syn::ItemImpl) -> pm::TokenStream {
+pub(crate) fn rewrite(_attr: syn::AttributeArgs, mut item: syn::ItemImpl) -> pm::TokenStream {
     use crate::new_id;
+    // Instances of type classes are only defined for sharable types, so they get the same
+    // bounds as the trait they implement. Their methods are called through the `Trait_method`
+    // functions of the trait, which are monomorphized to the instance, so no functions are
+    // generated for the instance itself.
+    if item.trait_.is_some() {
+        add_sharable_bounds(&mut item.generics);
+        for method in &mut item.items {
+            if let syn::ImplItem::Method(method) = method {
+                add_sharable_bounds(&mut method.sig.generics);
+            }
+        }
+        return quote!(#item).into();
+    }
     let mut functions = Vec::new();
     let ty_name = if let syn::Type::Path(x) = item.self_ty.as_ref() {
-        &x.path.segments.last().unwrap().ident
+        x.path.segments.last().unwrap().ident.clone()
     } else {
-        unreachable!("Found non-path type in impl")
+        return syn::Error::new_spanned(
+            &item.self_ty,
+            "#[rewrite] expects an impl of a named type",
+        )
+        .to_compile_error()
+        .into();
     };
-    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
-    for item in &item.items {
-        if let syn::ImplItem::Method(item) = item {
-            let mut inputs = item.sig.inputs.clone().into_iter().collect::<Vec<_>>();
-            let method_name = &item.sig.ident;
-            let name = new_id(format!("{}_{}", ty_name, method_name));
-            let output = &item.sig.output;
-            if matches!(inputs[0], syn::FnArg::Receiver(_)) {
-                inputs[0] = syn::parse_quote!(self_param: #ty_name #ty_generics);
-            }
-            let ctx = match inputs.pop().unwrap() {
-                syn::FnArg::Receiver(_) => unreachable!("Receiver in impl method"),
-                syn::FnArg::Typed(p) => p,
+    let (_, ty_generics, _) = item.generics.split_for_impl();
+    for method in &item.items {
+        if let syn::ImplItem::Method(method) = method {
+            let method_name = &method.sig.ident;
+            let mut replace = ReplaceSelf {
+                ty: item.self_ty.as_ref().clone(),
+                trait_: None,
             };
-            let ctx_id = ctx.pat;
-            let ctx_ty = ctx.ty;
-            let (ids, tys): (Vec<_>, Vec<_>) = inputs
-                .iter()
-                .map(|i| match i {
-                    syn::FnArg::Receiver(_) => unreachable!(),
-                    syn::FnArg::Typed(i) => (&i.pat, &i.ty),
-                })
-                .unzip();
-            functions.push(quote! {
-                pub fn #name #impl_generics ((#(#ids,)*):(#(#tys,)*), #ctx_id: #ctx_ty) #output #where_clause {
-                    #ty_name::#method_name(#(#ids,)* #ctx_id)
-                }
-            });
+            functions.push(wrapper(
+                new_id(format!("{}_{}", ty_name, method_name)),
+                item.generics.clone(),
+                &method.sig,
+                quote!(#ty_name #ty_generics),
+                quote!(#ty_name::#method_name),
+                &mut replace,
+            ));
         }
     }
 
@@ -46,3 +54,87 @@ pub(crate) fn rewrite(_attr: syn::AttributeArgs, item: syn::ItemImpl) -> pm::Tok
     )
     .into()
 }
+
+/// Generates a free function which takes the arguments of `sig` as a tuple and forwards them
+/// to `callee`. The generics of the method are added to `generics`, and `receiver` is the type
+/// of `self`.
+pub(crate) fn wrapper(
+    name: syn::Ident,
+    mut generics: syn::Generics,
+    sig: &syn::Signature,
+    receiver: pm2::TokenStream,
+    callee: pm2::TokenStream,
+    replace: &mut ReplaceSelf,
+) -> pm2::TokenStream {
+    // Methods may have generics of their own, which are added to those of the impl.
+    generics.params.extend(sig.generics.params.clone());
+    if let Some(w) = &sig.generics.where_clause {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(w.predicates.clone());
+    }
+    replace.visit_generics_mut(&mut generics);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let mut inputs = sig.inputs.clone().into_iter().collect::<Vec<_>>();
+    let mut output = sig.output.clone();
+    replace.visit_return_type_mut(&mut output);
+    if matches!(inputs[0], syn::FnArg::Receiver(_)) {
+        inputs[0] = syn::parse_quote!(self_param: #receiver);
+    }
+    let ctx = match inputs.pop().unwrap() {
+        syn::FnArg::Receiver(_) => unreachable!("Receiver in impl method"),
+        syn::FnArg::Typed(p) => p,
+    };
+    let ctx_id = ctx.pat;
+    let ctx_ty = ctx.ty;
+    let (ids, tys): (Vec<_>, Vec<_>) = inputs
+        .into_iter()
+        .map(|i| match i {
+            syn::FnArg::Receiver(_) => unreachable!(),
+            syn::FnArg::Typed(mut i) => {
+                replace.visit_type_mut(&mut i.ty);
+                (i.pat, i.ty)
+            }
+        })
+        .unzip();
+    quote! {
+        pub fn #name #impl_generics ((#(#ids,)*):(#(#tys,)*), #ctx_id: #ctx_ty) #output #where_clause {
+            #callee(#(#ids,)* #ctx_id)
+        }
+    }
+}
+
+/// Adds a `Sharable` bound to every type parameter, like rewritten functions do.
+pub(crate) fn add_sharable_bounds(generics: &mut syn::Generics) {
+    generics.params.iter_mut().for_each(|p| {
+        if let syn::GenericParam::Type(ref mut p) = *p {
+            p.bounds.push(syn::parse_quote!(Sharable));
+        }
+    });
+}
+
+/// Replaces `Self` with a concrete type so that signatures can be used outside of an impl.
+/// Associated types `Self::X` are qualified by `trait_` if there is one.
+pub(crate) struct ReplaceSelf {
+    pub(crate) ty: syn::Type,
+    pub(crate) trait_: std::option::Option<syn::Path>,
+}
+
+impl VisitMut for ReplaceSelf {
+    fn visit_type_mut(&mut self, i: &mut syn::Type) {
+        if let syn::Type::Path(p) = i {
+            if p.qself.is_none() && p.path.segments[0].ident == "Self" {
+                let ty = &self.ty;
+                let rest = p.path.segments.iter().skip(1).collect::<Vec<_>>();
+                *i = match (&self.trait_, rest.is_empty()) {
+                    (_, true) => ty.clone(),
+                    (Some(t), false) => syn::parse_quote!(<#ty as #t>#(::#rest)*),
+                    (None, false) => syn::parse_quote!(<#ty>#(::#rest)*),
+                };
+                return;
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, i);
+    }
+}
@@ -87,11 +87,10 @@ pub(crate) fn rewrite(attr: syn::AttributeArgs, item: syn::ItemFn) -> pm::TokenS
             }
 
             impl Task {
-                #[allow(deprecated)] // NOTE: DateTime::unix_epoch is deprecated
                 fn new(#(#iparam_name: #iparam_type,)* #(#oparam_name: #oparam_type,)*) -> Self {
                     Self {
                         ctx: ComponentContext::uninitialised(),
-                        event_time: DateTime::new(date!(1970-01-01), time!(0:0:0)),
+                        event_time: DateTime::EPOCH,
                         #(#iparam_name,)*
                         #(#oparam_name,)*
                     }
@@ -154,11 +153,11 @@ pub(crate) fn rewrite(attr: syn::AttributeArgs, item: syn::ItemFn) -> pm::TokenS
             }
 
             impl DynamicPortAccess for Task {
-                fn get_provided_port_as_any(&mut self, _: TypeId) -> Option<&mut dyn Any> {
+                fn get_provided_port_as_any(&mut self, _: TypeId) -> std::option::Option<&mut dyn Any> {
                     unreachable!();
                 }
 
-                fn get_required_port_as_any(&mut self, _: TypeId) -> Option<&mut dyn Any> {
+                fn get_required_port_as_any(&mut self, _: TypeId) -> std::option::Option<&mut dyn Any> {
                     unreachable!();
                 }
             }
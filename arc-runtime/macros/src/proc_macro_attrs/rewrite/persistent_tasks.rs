@@ -144,7 +144,7 @@ pub(crate) fn rewrite(attr: syn::AttributeArgs, item: syn::ItemMod) -> pm::Token
                 #(#transition)*
 
                 impl Future for Pair {
-                    type Output = ();
+                    type Output = Control<()>;
 
                     fn poll(self: Pin<&mut Self>, cx: &mut PollContext) -> Poll<Self::Output> {
                         cx.waker().wake_by_ref();
@@ -153,14 +153,15 @@ pub(crate) fn rewrite(attr: syn::AttributeArgs, item: syn::ItemMod) -> pm::Token
                     }
                 }
 
-                fn transition(mut state: State, cx: &mut PollContext, ctx: Context) -> (Poll<()>, State) {
+                fn transition(mut state: State, cx: &mut PollContext, ctx: Context) -> (Poll<Control<()>>, State) {
                     loop {
                         let (poll, new_state) = match state {
                             #(State::#state_name(state) => #transition_name(state, cx, ctx),)*
                         };
                         match &poll {
-                            Ready(()) if matches!(&new_state, State::#final_state_name(_)) => return (poll, new_state),
-                            Ready(()) => state = new_state,
+                            Ready(Failed(_)) => return (poll, new_state),
+                            Ready(_) if matches!(&new_state, State::#final_state_name(_)) => return (poll, new_state),
+                            Ready(_) => state = new_state,
                             Pending => return (poll, new_state),
                         }
                     }
@@ -209,7 +210,11 @@ pub(crate) fn rewrite(attr: syn::AttributeArgs, item: syn::ItemMod) -> pm::Token
                             #(let #iparam_name = async_self.#iparam_name.clone();)*
                             #(let #oparam_name = async_self.#oparam_name.clone();)*
                             let state = #first_state_name::new(#(#iparam_name,)* #(#oparam_name,)*).into();
-                            Pair(state, ctx).await;
+                            // A failure is passed on to the pullers of the outputs, as for other tasks.
+                            if let Control::Failed(e) = Pair(state, ctx).await {
+                                error!(async_self.log(), "Task failed: {}", e);
+                                #(async_self.#oparam_name.clone().fail(e.clone()).await;)*
+                            }
                             ctx.destroy();
                             Handled::DieNow
                         });
@@ -218,11 +223,11 @@ pub(crate) fn rewrite(attr: syn::AttributeArgs, item: syn::ItemMod) -> pm::Token
                 }
 
                 impl DynamicPortAccess for Task {
-                    fn get_provided_port_as_any(&mut self, _: TypeId) -> Option<&mut dyn Any> {
+                    fn get_provided_port_as_any(&mut self, _: TypeId) -> std::option::Option<&mut dyn Any> {
                         unreachable!();
                     }
 
-                    fn get_required_port_as_any(&mut self, _: TypeId) -> Option<&mut dyn Any> {
+                    fn get_required_port_as_any(&mut self, _: TypeId) -> std::option::Option<&mut dyn Any> {
                         unreachable!();
                     }
                 }
@@ -1,7 +1,10 @@
 use proc_macro as pm;
 use quote::quote;
 
+use crate::get_metas;
+use crate::has_meta_key;
 use crate::new_id;
+use crate::tagged;
 
 #[allow(unused)]
 pub(crate) fn rewrite(args: syn::AttributeArgs, mut item: syn::ItemStruct) -> pm::TokenStream {
@@ -49,6 +52,11 @@ pub(crate) fn rewrite(args: syn::AttributeArgs, mut item: syn::ItemStruct) -> pm
     let concrete_id = new_id(format!("Concrete{}", item.ident));
     let sharable_mod_id = new_id(format!("sharable_{}", item.ident));
     let sendable_mod_id = new_id(format!("sendable_{}", item.ident));
+    let tagged = tagged(
+        quote!(#sharable_mod_id::#abstract_id),
+        &abstract_id,
+        &item.generics,
+    );
 
     let mut concrete_sharable_item = item.clone();
     let mut concrete_sendable_item = item.clone();
@@ -98,6 +106,57 @@ pub(crate) fn rewrite(args: syn::AttributeArgs, mut item: syn::ItemStruct) -> pm
         .iter()
         .map(|f| &f.ident)
         .collect::<Vec<_>>();
+    let field_ty = item.fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    let arrow_impl = if has_meta_key("arrow", &get_metas(&args)) {
+        assert!(
+            item.generics.params.is_empty(),
+            "#[rewrite(arrow)] expects a struct without generics"
+        );
+        let column_id = field_id
+            .iter()
+            .map(|id| new_id(format!("column_{}", id.as_ref().unwrap())))
+            .collect::<Vec<_>>();
+        quote!(
+            impl Arrow for #sharable_mod_id::#abstract_id {
+                fn data_type() -> arc_runtime::data::arrow::DataType {
+                    arc_runtime::data::arrow::DataType::Struct(vec![
+                        #(arc_runtime::data::arrow::Field::new(
+                            stringify!(#field_id),
+                            <#field_ty as Arrow>::data_type(),
+                            false,
+                        )),*
+                    ])
+                }
+
+                fn to_array(values: &[Self]) -> std::sync::Arc<dyn arc_runtime::data::arrow::Array> {
+                    std::sync::Arc::new(arc_runtime::data::arrow::StructArray::from_data(
+                        Self::data_type(),
+                        vec![#(<#field_ty as Arrow>::to_array(
+                            &values.iter().map(|v| (v.0).#field_id.clone()).collect::<std::vec::Vec<_>>()
+                        )),*],
+                        None,
+                    ))
+                }
+
+                fn from_array(
+                    array: &dyn arc_runtime::data::arrow::Array,
+                    ctx: Context,
+                ) -> std::result::Result<std::vec::Vec<Self>, arc_runtime::control::Error> {
+                    let array = arc_runtime::data::arrow::downcast::<arc_runtime::data::arrow::StructArray>(array)?;
+                    let mut columns = array.values().iter();
+                    #(let mut #column_id = <#field_ty as Arrow>::from_array(&**columns.next().unwrap(), ctx)?.into_iter();)*
+                    Ok((0..array.len())
+                        .map(|_| #sharable_mod_id::#concrete_id {
+                            #(#field_id: #column_id.next().unwrap()),*
+                        }.alloc(ctx))
+                        .collect())
+                }
+            }
+        )
+    } else {
+        quote!()
+    };
 
     quote!(
 
@@ -120,7 +179,7 @@ pub(crate) fn rewrite(args: syn::AttributeArgs, mut item: syn::ItemStruct) -> pm
             #[derive(Clone, Debug, Deref, From, Abstract, Deserialize, Serialize)]
             #[serde(bound = "")]
             #[from(forward)]
-            pub struct #abstract_id #sendable_impl_generics(pub Box<#concrete_id #type_generics>) #where_clause;
+            pub struct #abstract_id #sendable_impl_generics(pub arc_runtime::data::graphs::Shared<#concrete_id #type_generics>) #where_clause;
 
             #[derive(Clone, Debug, Deserialize, Serialize)]
             #[serde(bound = "")]
@@ -130,23 +189,66 @@ pub(crate) fn rewrite(args: syn::AttributeArgs, mut item: syn::ItemStruct) -> pm
         use #sharable_mod_id::#abstract_id;
         use #sharable_mod_id::#concrete_id;
 
+        #tagged
+
         impl #sharable_impl_generics DynSharable for #sharable_mod_id::#abstract_id #type_generics #where_clause {
             type T = #sendable_mod_id::#abstract_id #into_generics;
             fn into_sendable(&self, ctx: Context) -> Self::T {
+                arc_runtime::data::graphs::into_sendable(self, ctx).into()
+            }
+            fn hollow(ctx: Context) -> std::option::Option<Self> {
+                arc_runtime::data::graphs::hollow(|| {
+                    Some(#sharable_mod_id::#concrete_id {
+                        #(#field_id: <#field_ty as DynSharable>::hollow(ctx)?),*
+                    }.alloc(ctx))
+                })
+            }
+        }
+
+        impl #sharable_impl_generics arc_runtime::data::graphs::Encode for #sharable_mod_id::#abstract_id #type_generics #where_clause {
+            type Node = #sendable_mod_id::#concrete_id #into_generics;
+            type Sendable = #sendable_mod_id::#abstract_id #into_generics;
+            fn key(&self) -> usize {
+                &*self.0 as *const _ as usize
+            }
+            fn encode(&self, ctx: Context) -> Self::Node {
                 #sendable_mod_id::#concrete_id {
                     #(#field_id: (self.0).#field_id.clone().into_sendable(ctx)),*
-                }.into()
+                }
             }
         }
 
+        impl #sharable_impl_generics Interpolate for #sharable_mod_id::#abstract_id #type_generics #where_clause {}
+
         impl #sendable_impl_generics DynSendable for #sendable_mod_id::#abstract_id #type_generics #where_clause {
             type T = #sharable_mod_id::#abstract_id #into_generics;
             fn into_sharable(&self, ctx: Context) -> Self::T {
+                arc_runtime::data::graphs::into_sharable(self, ctx)
+            }
+        }
+
+        impl #sendable_impl_generics arc_runtime::data::graphs::Decode for #sendable_mod_id::#abstract_id #type_generics #where_clause {
+            type Node = #sendable_mod_id::#concrete_id #type_generics;
+            type Concrete = #sharable_mod_id::#concrete_id #into_generics;
+            fn shared(&self) -> &arc_runtime::data::graphs::Shared<Self::Node> {
+                &self.0
+            }
+            fn decode(node: &Self::Node, ctx: Context) -> Self::Concrete {
                 #sharable_mod_id::#concrete_id {
-                    #(#field_id: (self.0).#field_id.into_sharable(ctx)),*
-                }.alloc(ctx)
+                    #(#field_id: node.#field_id.into_sharable(ctx)),*
+                }
+            }
+            fn alloc(concrete: Self::Concrete, ctx: Context) -> Self::T {
+                concrete.alloc(ctx)
+            }
+            fn fill(node: &Self::T, concrete: Self::Concrete, ctx: Context) {
+                let mut gc = node.0.clone();
+                *gc = concrete;
+                gc.write_barrier(ctx);
             }
         }
 
+        #arrow_impl
+
     ).into()
 }
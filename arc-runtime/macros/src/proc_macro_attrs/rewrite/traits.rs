@@ -0,0 +1,58 @@
+use proc_macro as pm;
+use quote::quote;
+
+use crate::new_id;
+use crate::proc_macro_attrs::rewrite::impls::add_sharable_bounds;
+use crate::proc_macro_attrs::rewrite::impls::wrapper;
+use crate::proc_macro_attrs::rewrite::impls::ReplaceSelf;
+
+/// Rewrites a type class into a trait whose instances are sharable. Every method also gets a
+/// free function `Trait_method` which is generic over the instance, so that a call through the
+/// class is monomorphized to the method of the instance. The instance can be picked explicitly
+/// with a turbofish, e.g. `Monoid_identity::<i32>`.
+pub(crate) fn rewrite(_attr: syn::AttributeArgs, mut item: syn::ItemTrait) -> pm::TokenStream {
+    add_sharable_bounds(&mut item.generics);
+    item.supertraits.push(syn::parse_quote!(Sharable));
+    for method in &mut item.items {
+        if let syn::TraitItem::Method(method) = method {
+            add_sharable_bounds(&mut method.sig.generics);
+        }
+    }
+    let trait_name = &item.ident;
+    let (_, ty_generics, _) = item.generics.split_for_impl();
+    let trait_path: syn::Path = syn::parse_quote!(#trait_name #ty_generics);
+    let instance: syn::Ident = new_id("Instance");
+    let mut generics = item.generics.clone();
+    generics
+        .params
+        .push(syn::parse_quote!(#instance: #trait_path));
+    let functions = item
+        .items
+        .iter()
+        .filter_map(|method| match method {
+            syn::TraitItem::Method(method) => Some(method),
+            _ => None,
+        })
+        .map(|method| {
+            let method_name = &method.sig.ident;
+            let mut replace = ReplaceSelf {
+                ty: syn::parse_quote!(#instance),
+                trait_: Some(trait_path.clone()),
+            };
+            wrapper(
+                new_id(format!("{}_{}", trait_name, method_name)),
+                generics.clone(),
+                &method.sig,
+                quote!(#instance),
+                quote!(<#instance as #trait_path>::#method_name),
+                &mut replace,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    quote::quote!(
+        #item
+        #(#functions)*
+    )
+    .into()
+}
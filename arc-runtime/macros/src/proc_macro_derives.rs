@@ -143,13 +143,13 @@ pub fn derive_noserde(input: syn::DeriveInput) -> TokenStream {
     let name = &input.ident;
     quote::quote!(
         impl #serialize_impl_generics Serialize for #name #type_generics #where_clause {
-            fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            fn serialize<S: Serializer>(&self, _: S) -> std::result::Result<S::Ok, S::Error> {
                 panic!("Attempted to serialize an unserializable type {}", stringify!(#name))
             }
         }
 
         impl #deserialize_impl_generics Deserialize<'i> for #name #type_generics #where_clause {
-            fn deserialize<D: Deserializer<'i>>(_: D) -> Result<Self, D::Error> {
+            fn deserialize<D: Deserializer<'i>>(_: D) -> std::result::Result<Self, D::Error> {
                 panic!("Attempted to deserialize an undeserializable type {}", stringify!(#name))
             }
         }
@@ -4,6 +4,8 @@ use proc_macro as pm;
 use proc_macro::TokenStream;
 use proc_macro2 as pm2;
 
+use std::collections::BTreeSet;
+
 pub fn call(input: syn::Expr) -> TokenStream {
     match input {
         syn::Expr::Call(e) => {
@@ -40,15 +42,29 @@ pub fn call_indirect(input: syn::Expr) -> TokenStream {
             let func = e.func;
             let args = e.args;
             if args.len() == 1 && !args.trailing_punct() {
-                quote::quote!((#func.ptr)((#args,), ctx)).into()
+                quote::quote!(#func.call((#args,), ctx)).into()
             } else {
-                quote::quote!((#func.ptr)((#args), ctx)).into()
+                quote::quote!(#func.call((#args), ctx)).into()
             }
         }
         _ => panic!("Expected function call expression"),
     }
 }
 
+/// Create a closure value from the code of a closure and the environment it captures.
+pub fn closure(input: TokenStream) -> TokenStream {
+    let mut iter = input.into_iter();
+    let id: syn::Ident = parse(&mut iter);
+    let env: syn::Expr = parse(&mut iter);
+    quote::quote!(
+        Function {
+            code: Code::Closure(arc_runtime::closure_code!(#id), Erased::erase(#env, ctx)),
+            tag: FunctionTag::new(Tag::#id),
+        }
+    )
+    .into()
+}
+
 pub fn enwrap(input: TokenStream) -> TokenStream {
     let mut iter = input.into_iter();
     let mut path: syn::Path = parse(&mut iter);
@@ -89,30 +105,15 @@ pub fn vector(input: TokenStream) -> TokenStream {
 pub fn erase(input: TokenStream) -> TokenStream {
     let mut iter = input.into_iter();
     let expr: syn::Expr = parse(&mut iter);
-    let ident: syn::Ident = parse(&mut iter);
-    let (wrapper_impl, wrapper_cons) = generate_wrapper(&ident);
-    let wrapper = wrapper_cons(expr);
-    quote::quote!(
-        {
-            #wrapper_impl
-            Erased::erase(#wrapper, ctx)
-        }
-    )
-    .into()
+    let id: syn::Ident = parse(&mut iter);
+    quote::quote!(Erased::erase::<#id>(#expr, ctx)).into()
 }
 
 pub fn unerase(input: TokenStream) -> TokenStream {
     let mut iter = input.into_iter();
     let expr: syn::Expr = parse(&mut iter);
     let id: syn::Ident = parse(&mut iter);
-    let (wrapper_impl, _) = generate_wrapper(&id);
-    quote::quote!(
-        {
-            #wrapper_impl
-            Erased::unerase::<#id>(#expr, ctx)
-        }
-    )
-    .into()
+    quote::quote!(Erased::unerase::<#id>(#expr, ctx).unwrap_or_else(|e| panic!("{}", e))).into()
 }
 
 pub fn push(input: TokenStream) -> TokenStream {
@@ -172,10 +173,10 @@ pub fn transition(input: TokenStream) -> TokenStream {
 pub fn terminate(input: TokenStream) -> TokenStream {
     let mut iter = input.into_iter();
     let state: syn::Expr = parse(&mut iter);
-    quote::quote!(return (Ready(()), #state.into())).into()
+    quote::quote!(return (Ready(Finished), #state.into())).into()
 }
 
-// /// Wait until a future completes.
+// /// Wait until a future completes. If the future fails, the task ends with its error.
 pub fn wait(input: TokenStream) -> TokenStream {
     let mut iter = input.into_iter();
     let arg: syn::Expr = parse(&mut iter);
@@ -185,6 +186,7 @@ pub fn wait(input: TokenStream) -> TokenStream {
     quote::quote!(
         match #arg.as_mut().poll(#cx) {
             Ready(Finished) => terminate!(#finished),
+            Ready(Failed(e)) => return (Ready(Failed(e)), #finished.into()),
             Ready(Continue(x)) => x,
             Pending => transition!(#pending),
         }
@@ -192,51 +194,270 @@ pub fn wait(input: TokenStream) -> TokenStream {
     .into()
 }
 
-fn generate_wrapper(id: &syn::Ident) -> (pm2::TokenStream, impl Fn(syn::Expr) -> pm2::TokenStream) {
-    let span = id.span().unwrap().start();
-    let line = span.line;
-    let column = span.column;
-    let abstract_id: syn::Ident = new_id(format!("Wrapper_{}_{}", line, column));
-    let concrete_id: syn::Ident = new_id(format!("ConcreteWrapper_{}_{}", line, column));
-    let sharable_wrapper_mod_id = new_id(format!("sharable_{}", abstract_id));
-    let sendable_wrapper_mod_id = new_id(format!("sendable_{}", abstract_id));
-    let wrapper_impl = quote::quote!(
-        mod #sharable_wrapper_mod_id {
-            use arc_runtime::prelude::*;
-            #[derive(Clone, Debug, Send, Sync, Unpin, From, Deref, Abstract, Collectable, Finalize, Trace)]
-            #[repr(transparent)]
-            pub struct #abstract_id(pub #concrete_id);
-            #[derive(Clone, Debug, Collectable, Finalize, Trace)]
-            #[repr(transparent)]
-            pub struct #concrete_id(pub super::#id);
+/// The sorted field names of a record, and whether it is open.
+struct Row {
+    fields: Vec<String>,
+    open: bool,
+}
+
+fn parse_row(input: syn::parse::ParseStream) -> syn::Result<Row> {
+    let content;
+    syn::braced!(content in input);
+    let mut fields = Vec::new();
+    let mut open = false;
+    while !content.is_empty() {
+        if content.peek(syn::Token![..]) {
+            content.parse::<syn::Token![..]>()?;
+            open = true;
+            break;
+        }
+        fields.push(content.parse::<syn::Ident>()?.to_string());
+        if !content.is_empty() {
+            content.parse::<syn::Token![,]>()?;
         }
+    }
+    fields.sort();
+    fields.dedup();
+    Ok(Row { fields, open })
+}
+
+/// Returns the name of the canonical struct of a record with the given sorted fields.
+fn record_id(open: bool, fields: &[String]) -> syn::Ident {
+    let prefix = if open { "OpenRecord" } else { "Record" };
+    new_id(
+        fields
+            .iter()
+            .fold(prefix.to_string(), |id, f| format!("{}__{}", id, f)),
+    )
+}
+
+fn record_ty(id: &syn::Ident, params: &[pm2::TokenStream]) -> pm2::TokenStream {
+    if params.is_empty() {
+        quote::quote!(#id)
+    } else {
+        quote::quote!(#id<#(#params),*>)
+    }
+}
+
+/// Declare the canonical structs of records, and conversions between each open record and the
+/// closed records which have its fields.
+pub fn declare_records(input: TokenStream) -> TokenStream {
+    use syn::parse::Parser;
+    let parser = |input: syn::parse::ParseStream| {
+        syn::punctuated::Punctuated::<Row, syn::Token![,]>::parse_terminated_with(input, parse_row)
+    };
+    let rows = match parser.parse(input) {
+        Ok(rows) => rows,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let mut closed = rows
+        .iter()
+        .filter(|r| !r.open)
+        .map(|r| r.fields.clone())
+        .collect::<BTreeSet<_>>();
+    let open = rows
+        .iter()
+        .filter(|r| r.open)
+        .map(|r| r.fields.clone())
+        .collect::<BTreeSet<_>>();
+    let pairs = open
+        .iter()
+        .flat_map(|k| closed.iter().map(move |c| (k.clone(), c.clone())))
+        .filter(|(k, c)| k.iter().all(|f| c.contains(f)))
+        .collect::<Vec<_>>();
+    // The rest of a closed record is erased as a closed record of the remaining fields
+    for (k, c) in &pairs {
+        closed.insert(c.iter().filter(|f| !k.contains(f)).cloned().collect());
+    }
 
-        mod #sendable_wrapper_mod_id {
+    let mut items = Vec::new();
+    let mut ids = Vec::new();
+    for (fields, is_open) in closed
+        .iter()
+        .map(|c| (c, false))
+        .chain(open.iter().map(|k| (k, true)))
+    {
+        let id = record_id(is_open, fields);
+        let field_id = fields.iter().map(new_id).collect::<Vec<_>>();
+        let param = (0..fields.len())
+            .map(|i| new_id(format!("T{}", i)))
+            .collect::<Vec<_>>();
+        let rest = if is_open {
+            quote::quote!(pub __rest: Erased,)
+        } else {
+            quote::quote!()
+        };
+        items.push(quote::quote!(
+            #[rewrite]
+            pub struct #id<#(#param),*> {
+                #(pub #field_id: #param,)*
+                #rest
+            }
+        ));
+        ids.push(id);
+    }
+
+    for (k, c) in &pairs {
+        let r = c
+            .iter()
+            .filter(|f| !k.contains(f))
+            .cloned()
+            .collect::<Vec<_>>();
+        let param = |fields: &[String]| {
+            fields
+                .iter()
+                .map(|f| {
+                    let i = new_id(format!("T{}", c.iter().position(|x| x == f).unwrap()));
+                    quote::quote!(#i)
+                })
+                .collect::<Vec<_>>()
+        };
+        let (c_id, k_id, r_id) = (
+            record_id(false, c),
+            record_id(true, k),
+            record_id(false, &r),
+        );
+        let (c_param, k_param, r_param) = (param(c), param(k), param(&r));
+        let c_ty = record_ty(&c_id, &c_param);
+        let k_ty = record_ty(&k_id, &k_param);
+        let r_ty = record_ty(&r_id, &r_param);
+        let c_concrete = new_id(format!("Concrete{}", c_id));
+        let k_concrete = new_id(format!("Concrete{}", k_id));
+        let r_concrete = new_id(format!("Concrete{}", r_id));
+        let k_field = k.iter().map(new_id).collect::<Vec<_>>();
+        let r_field = r.iter().map(new_id).collect::<Vec<_>>();
+        items.push(quote::quote!(
+            impl<#(#c_param: Sharable),*> Open<#k_ty> for #c_ty
+            where
+                #r_ty: Tagged + 'static,
+                <#r_ty as DynSharable>::T: DynSendable<T = #r_ty>,
+            {
+                fn open(self, ctx: Context) -> #k_ty {
+                    let rest = #r_concrete { #(#r_field: self.#r_field.clone()),* }.alloc(ctx);
+                    #k_concrete {
+                        #(#k_field: self.#k_field.clone(),)*
+                        __rest: Erased::erase(rest, ctx),
+                    }
+                    .alloc(ctx)
+                }
+            }
+
+            impl<#(#c_param: Sharable),*> Close<#c_ty> for #k_ty
+            where
+                #r_ty: Tagged + 'static,
+                <#r_ty as DynSharable>::T: DynSendable<T = #r_ty>,
+            {
+                fn close(
+                    self,
+                    ctx: Context,
+                ) -> std::result::Result<#c_ty, arc_runtime::data::erased::UneraseError> {
+                    let rest = self.__rest.clone().unerase::<#r_ty>(ctx)?;
+                    Ok(#c_concrete {
+                        #(#k_field: self.#k_field.clone(),)*
+                        #(#r_field: rest.#r_field.clone()),*
+                    }
+                    .alloc(ctx))
+                }
+            }
+        ));
+    }
+
+    let sharable_mod_id = ids
+        .iter()
+        .map(|id| new_id(format!("sharable_{}", id)))
+        .collect::<Vec<_>>();
+    let concrete_id = ids
+        .iter()
+        .map(|id| new_id(format!("Concrete{}", id)))
+        .collect::<Vec<_>>();
+    quote::quote!(
+        #[allow(non_camel_case_types, non_snake_case)]
+        pub mod records {
             use arc_runtime::prelude::*;
-            #[derive(Clone, Debug, Deref, From, Abstract, Serialize, Deserialize)]
-            #[repr(transparent)]
-            pub struct #abstract_id(pub #concrete_id);
-            #[derive(Clone, Debug, Serialize, Deserialize)]
-            #[repr(transparent)]
-            pub struct #concrete_id(pub <super::#id as DynSharable>::T);
+            #(#items)*
         }
+        #(
+            pub use records::#sharable_mod_id::#ids;
+            pub use records::#sharable_mod_id::#concrete_id;
+        )*
+    )
+    .into()
+}
 
-        impl DynSharable for #sharable_wrapper_mod_id::#abstract_id {
-            type T = <Erased as DynSharable>::T;
-            fn into_sendable(&self, ctx: Context) -> Self::T {
-                Self::T::erase(#sendable_wrapper_mod_id::#abstract_id(#sendable_wrapper_mod_id::#concrete_id(self.0.0.into_sendable(ctx))), ctx)
-            }
+/// A field of a record type or of a record value.
+enum RecordField {
+    Type(syn::Ident, syn::Type),
+    Value(syn::Ident, syn::Expr),
+    Rest(Option<syn::Expr>),
+}
+
+fn parse_record_field(input: syn::parse::ParseStream) -> syn::Result<RecordField> {
+    if input.peek(syn::Token![..]) {
+        input.parse::<syn::Token![..]>()?;
+        if input.peek(syn::Token![=]) {
+            input.parse::<syn::Token![=]>()?;
+            Ok(RecordField::Rest(Some(input.parse()?)))
+        } else {
+            Ok(RecordField::Rest(None))
+        }
+    } else {
+        let id = input.parse()?;
+        if input.peek(syn::Token![=]) {
+            input.parse::<syn::Token![=]>()?;
+            Ok(RecordField::Value(id, input.parse()?))
+        } else {
+            input.parse::<syn::Token![:]>()?;
+            Ok(RecordField::Type(id, input.parse()?))
         }
+    }
+}
 
-        impl DynSendable for #sendable_wrapper_mod_id::#abstract_id {
-            type T = Erased;
-            fn into_sharable(&self, ctx: Context) -> Self::T {
-                Self::T::erase(#sharable_wrapper_mod_id::#abstract_id(#sharable_wrapper_mod_id::#concrete_id(self.0.0.into_sharable(ctx))), ctx)
+/// Create a record type `record!(k: i32, v: i32)` or a record value `record!(k = 1, v = 2)`.
+/// Open records have a trailing `..` in their type and `.. = rest` in their value.
+pub fn record(input: TokenStream) -> TokenStream {
+    use syn::parse::Parser;
+    let parser = |input: syn::parse::ParseStream| {
+        syn::punctuated::Punctuated::<RecordField, syn::Token![,]>::parse_terminated_with(
+            input,
+            parse_record_field,
+        )
+    };
+    let mut types = Vec::new();
+    let mut values = Vec::new();
+    let mut rest = None;
+    let mut open = false;
+    let fields = match parser.parse(input) {
+        Ok(fields) => fields,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    for field in fields {
+        match field {
+            RecordField::Type(id, ty) => types.push((id.to_string(), quote::quote!(#ty))),
+            RecordField::Value(id, e) => values.push((id.to_string(), quote::quote!(#e))),
+            RecordField::Rest(e) => {
+                open = true;
+                rest = e;
             }
         }
+    }
+    assert!(
+        types.is_empty() || values.is_empty(),
+        "Expected either a record type or a record value"
     );
-    let wrapper_cons = move |expr| quote::quote!(#sharable_wrapper_mod_id::#abstract_id(#sharable_wrapper_mod_id::#concrete_id(#expr)));
-    (wrapper_impl, wrapper_cons)
+    if values.is_empty() && rest.is_none() {
+        types.sort_by(|a, b| a.0.cmp(&b.0));
+        let fields = types.iter().map(|(f, _)| f.clone()).collect::<Vec<_>>();
+        let params = types.into_iter().map(|(_, ty)| ty).collect::<Vec<_>>();
+        record_ty(&record_id(open, &fields), &params).into()
+    } else {
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+        let fields = values.iter().map(|(f, _)| f.clone()).collect::<Vec<_>>();
+        let concrete_id = new_id(format!("Concrete{}", record_id(open, &fields)));
+        let field_id = fields.iter().map(new_id);
+        let value = values.iter().map(|(_, e)| e);
+        let rest = rest.map(|e| quote::quote!(__rest: #e,));
+        quote::quote!(#concrete_id { #(#field_id: #value,)* #rest }.alloc(ctx)).into()
+    }
 }
 
 fn concrete_enum_path(path: &mut syn::Path) {
@@ -4,9 +4,12 @@ use comet::mutator::MutatorRef;
 use derive_more::Constructor as New;
 use kompact::prelude::*;
 
+use crate::control::Control;
 use crate::prelude::Send;
 use crate::prelude::Sync;
 use crate::prelude::Unpin;
+use crate::task::anonymous::AnonymousTask;
+use std::future::Future;
 use std::sync::Arc;
 
 /// The context of a single task.
@@ -56,4 +59,12 @@ impl Context {
         let c = system.create(f);
         system.start(&c);
     }
+    /// Launches a task which runs the future returned by `f` with a context of its own.
+    pub fn spawn<F, Fut>(&self, f: F)
+    where
+        F: FnOnce(Context) -> Fut + Send + 'static,
+        Fut: Future<Output = Control<()>> + 'static,
+    {
+        self.launch(move || AnonymousTask::new(f))
+    }
 }
@@ -1,29 +1,59 @@
+use std::convert::Infallible;
+use std::fmt;
 use std::ops::ControlFlow;
 use std::ops::FromResidual;
 use std::ops::Try;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub enum Control<T> {
     Continue(T),
     Finished,
+    /// Processing stopped because of an error, which is passed on to the pullers of the
+    /// channels which the failed task pushes into.
+    Failed(Error),
 }
 
-impl<T> FromResidual<()> for Control<T> {
-    fn from_residual(residual: ()) -> Self {
-        Control::Finished
+/// An error which stops a task. Errors are cheap to clone, since every puller of a channel
+/// receives its own copy.
+#[derive(Clone, Debug)]
+pub struct Error(Arc<str>);
+
+impl Error {
+    pub fn new(message: impl fmt::Display) -> Self {
+        Error(message.to_string().into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl<T> FromResidual<Control<Infallible>> for Control<T> {
+    fn from_residual(residual: Control<Infallible>) -> Self {
+        match residual {
+            Control::Continue(never) => match never {},
+            Control::Finished => Control::Finished,
+            Control::Failed(error) => Control::Failed(error),
+        }
     }
 }
 
 impl<T> Try for Control<T> {
     type Output = T;
-    type Residual = ();
+    type Residual = Control<Infallible>;
     fn from_output(output: T) -> Self {
         Control::Continue(output)
     }
-    fn branch(self) -> ControlFlow<(), T> {
+    fn branch(self) -> ControlFlow<Control<Infallible>, T> {
         match self {
             Control::Continue(output) => ControlFlow::Continue(output),
-            Control::Finished => ControlFlow::Break(()),
+            Control::Finished => ControlFlow::Break(Control::Finished),
+            Control::Failed(error) => ControlFlow::Break(Control::Failed(error)),
         }
     }
 }
@@ -0,0 +1,232 @@
+//! Conversion between vectors of records and Arrow record batches.
+//!
+//! A struct declared with `#[rewrite(arrow)]` implements [`Arrow`], and a `Vec` of it converts
+//! into a [`Chunk`] with one column per field. Primitive fields map to Arrow primitive arrays,
+//! `String` fields to utf8 arrays, `Vec` fields to list arrays and struct fields to struct arrays.
+//! Records have no null values, so arrays with nulls are rejected when converting back.
+
+use crate::control::Error;
+use crate::prelude::*;
+
+pub use arrow2::array::Array;
+pub use arrow2::array::BooleanArray;
+pub use arrow2::array::ListArray;
+pub use arrow2::array::PrimitiveArray;
+pub use arrow2::array::StructArray;
+pub use arrow2::array::Utf8Array;
+pub use arrow2::chunk::Chunk;
+pub use arrow2::datatypes::DataType;
+pub use arrow2::datatypes::Field;
+pub use arrow2::datatypes::Schema;
+
+use arrow2::io::ipc::read::read_file_metadata;
+use arrow2::io::ipc::read::FileReader;
+use arrow2::io::ipc::write::FileWriter;
+use arrow2::io::ipc::write::WriteOptions;
+
+use std::sync::Arc;
+
+/// A type of value which can be stored in an Arrow array.
+pub trait Arrow: Sharable {
+    fn data_type() -> DataType;
+    fn to_array(values: &[Self]) -> Arc<dyn Array>;
+    fn from_array(
+        array: &dyn Array,
+        ctx: Context,
+    ) -> std::result::Result<std::vec::Vec<Self>, Error>;
+}
+
+/// Downcasts an array to its concrete type, which is known from the static type of its values.
+/// Fails if the array is of another type, or if it has nulls.
+pub fn downcast<A: Array + 'static>(array: &dyn Array) -> std::result::Result<&A, Error> {
+    let data = array
+        .as_any()
+        .downcast_ref::<A>()
+        .ok_or_else(|| Error::new(format!("Unexpected array of type {:?}", array.data_type())))?;
+    if array.null_count() > 0 {
+        return Err(Error::new(format!(
+            "Unexpected {} nulls in array of type {:?}",
+            array.null_count(),
+            array.data_type()
+        )));
+    }
+    Ok(data)
+}
+
+macro_rules! impl_arrow_primitive {
+    ($($ty:ty => $dtype:ident),*) => {
+        $(
+            impl Arrow for $ty {
+                fn data_type() -> DataType {
+                    DataType::$dtype
+                }
+
+                fn to_array(values: &[Self]) -> Arc<dyn Array> {
+                    Arc::new(PrimitiveArray::<$ty>::from_slice(values))
+                }
+
+                fn from_array(
+                    array: &dyn Array,
+                    ctx: Context,
+                ) -> std::result::Result<std::vec::Vec<Self>, Error> {
+                    Ok(downcast::<PrimitiveArray<$ty>>(array)?.values().to_vec())
+                }
+            }
+        )*
+    };
+}
+
+impl_arrow_primitive!(
+    i8 => Int8,
+    i16 => Int16,
+    i32 => Int32,
+    i64 => Int64,
+    u8 => UInt8,
+    u16 => UInt16,
+    u32 => UInt32,
+    u64 => UInt64,
+    f32 => Float32,
+    f64 => Float64
+);
+
+impl Arrow for bool {
+    fn data_type() -> DataType {
+        DataType::Boolean
+    }
+
+    fn to_array(values: &[Self]) -> Arc<dyn Array> {
+        Arc::new(BooleanArray::from_slice(values))
+    }
+
+    fn from_array(
+        array: &dyn Array,
+        ctx: Context,
+    ) -> std::result::Result<std::vec::Vec<Self>, Error> {
+        Ok(downcast::<BooleanArray>(array)?.values_iter().collect())
+    }
+}
+
+impl Arrow for String {
+    fn data_type() -> DataType {
+        DataType::Utf8
+    }
+
+    fn to_array(values: &[Self]) -> Arc<dyn Array> {
+        let values = values
+            .iter()
+            .map(|s| s.as_str())
+            .collect::<std::vec::Vec<_>>();
+        Arc::new(Utf8Array::<i32>::from_slice(values))
+    }
+
+    fn from_array(
+        array: &dyn Array,
+        ctx: Context,
+    ) -> std::result::Result<std::vec::Vec<Self>, Error> {
+        Ok(downcast::<Utf8Array<i32>>(array)?
+            .values_iter()
+            .map(|s| String::from_str(s, ctx))
+            .collect())
+    }
+}
+
+impl<T: Arrow> Arrow for Vec<T> {
+    fn data_type() -> DataType {
+        ListArray::<i32>::default_datatype(T::data_type())
+    }
+
+    fn to_array(values: &[Self]) -> Arc<dyn Array> {
+        let mut offsets = std::vec::Vec::with_capacity(values.len() + 1);
+        let mut items = std::vec::Vec::new();
+        offsets.push(0);
+        for v in values {
+            items.extend_from_slice(v.0.as_slice());
+            offsets.push(items.len() as i32);
+        }
+        Arc::new(ListArray::<i32>::from_data(
+            Self::data_type(),
+            offsets.into(),
+            T::to_array(&items),
+            None,
+        ))
+    }
+
+    fn from_array(
+        array: &dyn Array,
+        ctx: Context,
+    ) -> std::result::Result<std::vec::Vec<Self>, Error> {
+        let array = downcast::<ListArray<i32>>(array)?;
+        // The offsets of a sliced array start after zero, and only the items between the first
+        // and the last offset belong to it.
+        let offsets = array.offsets();
+        let start = offsets[0] as usize;
+        let end = offsets[offsets.len() - 1] as usize;
+        let items = T::from_array(&*array.values().slice(start, end - start), ctx)?;
+        Ok(offsets
+            .windows(2)
+            .map(|w| {
+                let range = w[0] as usize - start..w[1] as usize - start;
+                Vec::from_iter(items[range].to_vec(), ctx)
+            })
+            .collect())
+    }
+}
+
+/// Returns the schema of record batches of `T`, which must be a struct.
+pub fn schema<T: Arrow>() -> Schema {
+    match T::data_type() {
+        DataType::Struct(fields) => Schema::from(fields),
+        ty => panic!("Expected a struct type, found {:?}", ty),
+    }
+}
+
+/// Converts records into a record batch with one column per field.
+pub fn to_chunk<T: Arrow>(values: Vec<T>, ctx: Context) -> Chunk<Arc<dyn Array>> {
+    let array = T::to_array(values.as_slice(ctx));
+    let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+    Chunk::new(array.values().to_vec())
+}
+
+/// Converts a record batch with one column per field into records. Fails if a column has
+/// nulls.
+pub fn from_chunk<T: Arrow>(
+    chunk: &Chunk<Arc<dyn Array>>,
+    ctx: Context,
+) -> std::result::Result<Vec<T>, Error> {
+    Ok(Vec::from_iter(records(chunk, ctx)?, ctx))
+}
+
+fn records<T: Arrow>(
+    chunk: &Chunk<Arc<dyn Array>>,
+    ctx: Context,
+) -> std::result::Result<std::vec::Vec<T>, Error> {
+    let array = StructArray::from_data(T::data_type(), chunk.arrays().to_vec(), None);
+    T::from_array(&array, ctx)
+}
+
+/// Writes records to a file in the Arrow IPC format.
+pub fn write_ipc<T: Arrow>(path: &str, values: Vec<T>, ctx: Context) {
+    let file = std::fs::File::create(path)
+        .unwrap_or_else(|e| panic!("Failed to create {:?}: {}", path, e));
+    let options = WriteOptions { compression: None };
+    let mut writer = FileWriter::try_new(file, &schema::<T>(), None, options)
+        .unwrap_or_else(|e| panic!("Failed to write {:?}: {}", path, e));
+    writer
+        .write(&to_chunk(values, ctx), None)
+        .and_then(|_| writer.finish())
+        .unwrap_or_else(|e| panic!("Failed to write {:?}: {}", path, e));
+}
+
+/// Reads records from a file in the Arrow IPC format. Fails if the file cannot be read, or if
+/// its record batches cannot be converted.
+pub fn read_ipc<T: Arrow>(path: &str, ctx: Context) -> std::result::Result<Vec<T>, Error> {
+    let failed = |e: &dyn std::fmt::Display| Error::new(format!("Failed to read {}: {}", path, e));
+    let mut file = std::fs::File::open(path).map_err(|e| failed(&e))?;
+    let metadata = read_file_metadata(&mut file).map_err(|e| failed(&e))?;
+    let mut values = std::vec::Vec::new();
+    for chunk in FileReader::new(file, metadata, None) {
+        let chunk = chunk.map_err(|e| failed(&e))?;
+        values.extend(records::<T>(&chunk, ctx).map_err(|e| failed(&e))?);
+    }
+    Ok(Vec::from_iter(values, ctx))
+}
@@ -0,0 +1,197 @@
+//! Integers of arbitrary precision, backed by `num-bigint`. A big integer is sent as its
+//! two's complement bytes in little-endian order.
+
+use crate::prelude::*;
+
+use num_bigint::Sign;
+use num_traits::Signed;
+use num_traits::ToPrimitive;
+use num_traits::Zero;
+
+pub mod sharable {
+    use crate::prelude::*;
+
+    #[derive(Clone, From, Deref, Debug, Collectable, Finalize, Send, Sync, Unpin, NoTrace)]
+    #[from(forward)]
+    pub struct BigInt(pub Gc<ConcreteBigInt>);
+
+    #[derive(Debug, Collectable, NoTrace, Finalize, Send, Sync, Unpin)]
+    pub struct ConcreteBigInt(pub num_bigint::BigInt);
+
+    impl Alloc<BigInt> for ConcreteBigInt {
+        fn alloc(self, ctx: Context) -> BigInt {
+            BigInt(ctx.mutator().allocate(self, AllocationSpace::New).into())
+        }
+    }
+
+    /// Big integers are compared and hashed by value so that they can be used as keys.
+    impl PartialEq for BigInt {
+        fn eq(&self, other: &Self) -> bool {
+            self.value() == other.value()
+        }
+    }
+
+    impl Eq for BigInt {}
+
+    impl Hash for BigInt {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.value().hash(state)
+        }
+    }
+
+    impl PartialOrd for BigInt {
+        fn partial_cmp(&self, other: &Self) -> std::option::Option<std::cmp::Ordering> {
+            Some(Ord::cmp(self, other))
+        }
+    }
+
+    impl Ord for BigInt {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.value().cmp(other.value())
+        }
+    }
+}
+
+pub mod sendable {
+    use crate::prelude::*;
+
+    #[derive(Clone, From, Send, Serialize, Deserialize)]
+    #[from(forward)]
+    pub struct BigInt(pub ConcreteBigInt);
+
+    pub type ConcreteBigInt = Box<[u8]>;
+}
+
+impl DynSharable for sharable::BigInt {
+    type T = sendable::BigInt;
+    fn into_sendable(&self, ctx: Context) -> Self::T {
+        self.value().to_signed_bytes_le().into_boxed_slice().into()
+    }
+    fn hollow(ctx: Context) -> std::option::Option<Self> {
+        Some(BigInt::from_i64(0, ctx))
+    }
+}
+
+impl DynSendable for sendable::BigInt {
+    type T = sharable::BigInt;
+    fn into_sharable(&self, ctx: Context) -> Self::T {
+        BigInt::wrap(num_bigint::BigInt::from_signed_bytes_le(&self.0), ctx)
+    }
+}
+
+pub use sharable::BigInt;
+
+impl Interpolate for BigInt {
+    fn display(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.value(), f)
+    }
+}
+
+impl BigInt {
+    pub(crate) fn wrap(value: num_bigint::BigInt, ctx: Context) -> BigInt {
+        sharable::ConcreteBigInt(value).alloc(ctx)
+    }
+
+    pub(crate) fn value(&self) -> &num_bigint::BigInt {
+        &self.0 .0
+    }
+}
+
+#[rewrite]
+impl BigInt {
+    pub fn from_i64(v: i64, ctx: Context) -> BigInt {
+        BigInt::wrap(v.into(), ctx)
+    }
+
+    /// Parses a decimal integer with an optional sign.
+    pub fn parse(s: String, ctx: Context) -> Option<BigInt> {
+        let s: &str = &s.0;
+        s.parse().ok().map(|v| BigInt::wrap(v, ctx)).alloc(ctx)
+    }
+
+    pub fn to_string(self, ctx: Context) -> String {
+        String::from_str(&self.value().to_string(), ctx)
+    }
+
+    /// Returns the value as an `i64`, or `OptionNone` if it does not fit.
+    pub fn to_i64(self, ctx: Context) -> Option<i64> {
+        self.value().to_i64().alloc(ctx)
+    }
+
+    pub fn add(self, other: BigInt, ctx: Context) -> BigInt {
+        BigInt::wrap(self.value() + other.value(), ctx)
+    }
+
+    pub fn sub(self, other: BigInt, ctx: Context) -> BigInt {
+        BigInt::wrap(self.value() - other.value(), ctx)
+    }
+
+    pub fn mul(self, other: BigInt, ctx: Context) -> BigInt {
+        BigInt::wrap(self.value() * other.value(), ctx)
+    }
+
+    /// Divides rounding towards zero, or returns `OptionNone` if `other` is zero.
+    pub fn div(self, other: BigInt, ctx: Context) -> Option<BigInt> {
+        if other.value().is_zero() {
+            Option::none(ctx)
+        } else {
+            Option::some(BigInt::wrap(self.value() / other.value(), ctx), ctx)
+        }
+    }
+
+    /// The remainder of [`BigInt::div`], which has the sign of `self`.
+    pub fn rem(self, other: BigInt, ctx: Context) -> Option<BigInt> {
+        if other.value().is_zero() {
+            Option::none(ctx)
+        } else {
+            Option::some(BigInt::wrap(self.value() % other.value(), ctx), ctx)
+        }
+    }
+
+    pub fn neg(self, ctx: Context) -> BigInt {
+        BigInt::wrap(-self.value(), ctx)
+    }
+
+    pub fn abs(self, ctx: Context) -> BigInt {
+        BigInt::wrap(self.value().abs(), ctx)
+    }
+
+    /// Raises `self` to the power of `exp`. The result has about `exp` times as many bits as
+    /// `self`, so a large `exp` takes time and memory in proportion, and can exhaust memory.
+    pub fn pow(self, exp: u32, ctx: Context) -> BigInt {
+        BigInt::wrap(self.value().pow(exp), ctx)
+    }
+
+    /// Returns -1, 0 or 1 if the value is negative, zero or positive.
+    pub fn signum(self, ctx: Context) -> i32 {
+        match self.value().sign() {
+            Sign::Minus => -1,
+            Sign::NoSign => 0,
+            Sign::Plus => 1,
+        }
+    }
+
+    pub fn eq(self, other: BigInt, ctx: Context) -> bool {
+        self == other
+    }
+
+    pub fn neq(self, other: BigInt, ctx: Context) -> bool {
+        self != other
+    }
+
+    pub fn lt(self, other: BigInt, ctx: Context) -> bool {
+        self < other
+    }
+
+    pub fn lt_eq(self, other: BigInt, ctx: Context) -> bool {
+        self <= other
+    }
+
+    pub fn gt(self, other: BigInt, ctx: Context) -> bool {
+        self > other
+    }
+
+    pub fn gt_eq(self, other: BigInt, ctx: Context) -> bool {
+        self >= other
+    }
+}
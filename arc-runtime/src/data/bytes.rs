@@ -0,0 +1,208 @@
+//! Immutable byte strings for raw payloads such as network frames and file contents. Bytes are
+//! copied in bulk, and are serialized as a single byte string instead of as a sequence.
+
+use crate::prelude::*;
+
+pub mod sharable {
+    use crate::prelude::*;
+
+    #[derive(Clone, From, Deref, Debug, Collectable, Finalize, Send, Sync, Unpin, NoTrace)]
+    #[from(forward)]
+    pub struct Bytes(pub Gc<ConcreteBytes>);
+
+    #[derive(Debug, Collectable, NoTrace, Finalize, Send, Sync, Unpin)]
+    pub struct ConcreteBytes(pub Box<[u8]>);
+
+    impl Alloc<Bytes> for ConcreteBytes {
+        fn alloc(self, ctx: Context) -> Bytes {
+            Bytes(ctx.mutator().allocate(self, AllocationSpace::New).into())
+        }
+    }
+
+    /// Bytes are compared and hashed by contents so that they can be used as keys.
+    impl PartialEq for Bytes {
+        fn eq(&self, other: &Self) -> bool {
+            self.as_slice() == other.as_slice()
+        }
+    }
+
+    impl Eq for Bytes {}
+
+    impl Hash for Bytes {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.as_slice().hash(state)
+        }
+    }
+}
+
+pub mod sendable {
+    use crate::prelude::*;
+
+    use serde::de::SeqAccess;
+    use serde::de::Visitor;
+
+    use std::fmt;
+
+    #[derive(Clone, Debug, From, Send)]
+    #[from(forward)]
+    pub struct Bytes(pub ConcreteBytes);
+
+    pub type ConcreteBytes = Box<[u8]>;
+
+    impl Serialize for Bytes {
+        fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+
+    impl<'i> Deserialize<'i> for Bytes {
+        fn deserialize<D: Deserializer<'i>>(
+            deserializer: D,
+        ) -> std::result::Result<Self, D::Error> {
+            deserializer.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+
+    /// Accepts byte strings, and sequences of bytes from formats without byte strings.
+    struct BytesVisitor;
+
+    impl<'i> Visitor<'i> for BytesVisitor {
+        type Value = Bytes;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a byte string")
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> std::result::Result<Bytes, E> {
+            Ok(Bytes(v.into()))
+        }
+
+        fn visit_byte_buf<E>(self, v: std::vec::Vec<u8>) -> std::result::Result<Bytes, E> {
+            Ok(Bytes(v.into_boxed_slice()))
+        }
+
+        fn visit_seq<A: SeqAccess<'i>>(self, mut seq: A) -> std::result::Result<Bytes, A::Error> {
+            let mut v = std::vec::Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(b) = seq.next_element()? {
+                v.push(b);
+            }
+            Ok(Bytes(v.into_boxed_slice()))
+        }
+    }
+}
+
+impl DynSharable for sharable::Bytes {
+    type T = sendable::Bytes;
+    fn into_sendable(&self, ctx: Context) -> Self::T {
+        sendable::Bytes(self.as_slice().into())
+    }
+    fn hollow(ctx: Context) -> std::option::Option<Self> {
+        Some(Bytes::new(ctx))
+    }
+}
+
+impl DynSendable for sendable::Bytes {
+    type T = sharable::Bytes;
+    fn into_sharable(&self, ctx: Context) -> Self::T {
+        Bytes::from_slice(&self.0, ctx)
+    }
+}
+
+pub use sharable::Bytes;
+
+impl Interpolate for Bytes {}
+
+impl Bytes {
+    pub fn from_slice(bytes: &[u8], ctx: Context) -> Bytes {
+        sharable::ConcreteBytes(bytes.into()).alloc(ctx)
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0 .0
+    }
+}
+
+#[rewrite]
+impl Bytes {
+    pub fn new(ctx: Context) -> Bytes {
+        Bytes::from_slice(&[], ctx)
+    }
+
+    pub fn from_vec(values: Vec<u8>, ctx: Context) -> Bytes {
+        Bytes::from_slice(values.as_slice(ctx), ctx)
+    }
+
+    pub fn to_vec(self, ctx: Context) -> Vec<u8> {
+        Vec::from_iter(self.as_slice().to_vec(), ctx)
+    }
+
+    /// Returns the UTF-8 encoding of `s`.
+    pub fn from_string(s: String, ctx: Context) -> Bytes {
+        let s: &str = &s.0;
+        Bytes::from_slice(s.as_bytes(), ctx)
+    }
+
+    /// Decodes the bytes as UTF-8, or returns `OptionNone` if they are not valid UTF-8.
+    pub fn to_string(self, ctx: Context) -> Option<String> {
+        std::str::from_utf8(self.as_slice())
+            .ok()
+            .map(|s| String::from_str(s, ctx))
+            .alloc(ctx)
+    }
+
+    pub fn len(self, ctx: Context) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(self, ctx: Context) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    pub fn get(self, index: usize, ctx: Context) -> Option<u8> {
+        self.as_slice().get(index).copied().alloc(ctx)
+    }
+
+    /// Returns a copy of the bytes in `[start, end)`, or `None` if the range is out of bounds.
+    pub fn slice(self, start: usize, end: usize, ctx: Context) -> Option<Bytes> {
+        self.as_slice()
+            .get(start..end)
+            .map(|bytes| Bytes::from_slice(bytes, ctx))
+            .alloc(ctx)
+    }
+
+    pub fn concat(self, other: Bytes, ctx: Context) -> Bytes {
+        Bytes::from_slice(&[self.as_slice(), other.as_slice()].concat(), ctx)
+    }
+
+    pub fn eq(self, other: Bytes, ctx: Context) -> bool {
+        self == other
+    }
+
+    /// Encodes the bytes as lowercase hexadecimal digits.
+    pub fn to_hex(self, ctx: Context) -> String {
+        String::from_str(&hex::encode(self.as_slice()), ctx)
+    }
+
+    /// Decodes hexadecimal digits, or returns `OptionNone` if `s` is not valid hexadecimal.
+    pub fn from_hex(s: String, ctx: Context) -> Option<Bytes> {
+        let s: &str = &s.0;
+        hex::decode(s)
+            .ok()
+            .map(|v| Bytes::from_slice(&v, ctx))
+            .alloc(ctx)
+    }
+
+    /// Encodes the bytes in the standard base64 alphabet, with padding.
+    pub fn to_base64(self, ctx: Context) -> String {
+        String::from_str(&base64::encode(self.as_slice()), ctx)
+    }
+
+    /// Decodes standard base64 with padding, or returns `OptionNone` if `s` is not valid.
+    pub fn from_base64(s: String, ctx: Context) -> Option<Bytes> {
+        let s: &str = &s.0;
+        base64::decode(s)
+            .ok()
+            .map(|v| Bytes::from_slice(&v, ctx))
+            .alloc(ctx)
+    }
+}
@@ -18,6 +18,9 @@ impl<T: Sharable> DynSharable for sharable::Cell<T> {
     fn into_sendable(&self, ctx: Context) -> Self::T {
         sendable::Cell(self.0.into_sendable(ctx))
     }
+    fn hollow(ctx: Context) -> std::option::Option<Self> {
+        T::hollow(ctx).map(|v| Cell::new(v, ctx))
+    }
 }
 
 impl<T: Sendable> DynSendable for sendable::Cell<T> {
@@ -29,15 +32,55 @@ impl<T: Sendable> DynSendable for sendable::Cell<T> {
 
 pub use sharable::Cell;
 
+/// A mutable memory location. Clones of a cell alias the same location, so a write through
+/// one of them is observed by all others.
 #[rewrite]
 impl<T: Sharable> Cell<T> {
     pub fn new(v: T, ctx: Context) -> Cell<T> {
         Cell(ctx.mutator().allocate(v, AllocationSpace::New).into())
     }
+
     pub fn get(self, ctx: Context) -> T {
         self.0.inner()
     }
+
     pub fn set(mut self, v: T, ctx: Context) {
-        self.0 = ctx.mutator().allocate(v, AllocationSpace::New).into();
+        *self.0 = v;
+        self.0.write_barrier(ctx);
+    }
+
+    /// Stores `v` and returns the previous value.
+    pub fn replace(mut self, v: T, ctx: Context) -> T {
+        let old = std::mem::replace(&mut *self.0, v);
+        self.0.write_barrier(ctx);
+        old
+    }
+
+    /// Applies `f` to the current value and stores the result.
+    pub fn update<F: Call<(T,), T>>(self, f: F, ctx: Context) {
+        let v = f.call((self.0.clone().inner(),), ctx);
+        self.set(v, ctx);
+    }
+
+    /// Exchanges the values of two cells.
+    pub fn swap(mut self, mut other: Cell<T>, ctx: Context) {
+        if !std::ptr::eq(&*self.0, &*other.0) {
+            std::mem::swap(&mut *self.0, &mut *other.0);
+            self.0.write_barrier(ctx);
+            other.0.write_barrier(ctx);
+        }
+    }
+
+    /// Stores `new` if the current value equals `current`, and returns whether it did.
+    pub fn compare_and_set(self, current: T, new: T, ctx: Context) -> bool
+    where
+        T: PartialEq,
+    {
+        if *self.0 == current {
+            self.set(new, ctx);
+            true
+        } else {
+            false
+        }
     }
 }
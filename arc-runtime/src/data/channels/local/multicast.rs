@@ -1,15 +1,21 @@
+use futures::Sink;
+use futures::SinkExt;
+use futures::Stream;
+use futures::StreamExt;
 use kompact::prelude::*;
 use std::marker::PhantomData;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::broadcast::Receiver;
 use tokio::sync::broadcast::Sender;
 
 use crate::control::Control;
+use crate::control::Error;
 use crate::data::Sharable;
 
 use crate::prelude::*;
 
 #[derive(Collectable, Finalize, NoTrace, NoSerde, NoDebug)]
-pub struct Pushable<T: Sharable>(Sender<T::T>);
+pub struct Pushable<T: Sharable>(Sender<Message<T::T>>);
 
 impl<T: Sharable> Clone for Pushable<T> {
     fn clone(&self) -> Self {
@@ -18,11 +24,24 @@ impl<T: Sharable> Clone for Pushable<T> {
 }
 
 #[derive(Collectable, Finalize, NoTrace, NoSerde, NoDebug)]
-pub struct Pullable<T: Sharable>(Sender<T::T>, Receiver<T::T>);
+pub struct Pullable<T: Sharable>(State<Message<T::T>>);
+
+/// Data, or the error which failed the channel.
+pub type Message<T> = std::result::Result<T, Error>;
+
+enum State<T> {
+    Idle(Receiver<T>),
+    /// A receive which was started by polling the pullable as a `Stream` and has not yet
+    /// completed. The receiver is owned by the future until it does.
+    Polling(Pin<Box<dyn Future<Output = (std::result::Result<T, RecvError>, Receiver<T>)> + Send + Sync>>),
+}
 
 impl<T: Sharable> Clone for Pullable<T> {
     fn clone(&self) -> Self {
-        Pullable(self.0.clone(), self.0.subscribe())
+        match &self.0 {
+            State::Idle(r) => Pullable(State::Idle(r.resubscribe())),
+            State::Polling(_) => panic!("Cannot clone a pullable while it is being polled"),
+        }
     }
 }
 
@@ -31,30 +50,173 @@ crate::data::convert_reflexive!({T: Sharable} Pullable<T>);
 
 crate::data::channels::impl_channel!();
 
-/// TODO: Processing will currently only stop if all pullers are dropped.
+/// How many elements a channel retains at least for pullers which are behind.
+pub const CAPACITY: usize = 100;
+
+/// Processing stops when either all pushers or all pullers are dropped. A puller which falls
+/// too far behind the pushers fails, see [`CAPACITY`].
 pub fn channel<T: Sharable>(_: Context) -> (Pushable<T>, Pullable<T>)
 where
     T::T: Sendable,
 {
-    let (l, r) = tokio::sync::broadcast::channel(100);
-    (Pushable(l.clone()), Pullable(l, r))
+    let (l, r) = tokio::sync::broadcast::channel(CAPACITY);
+    (Pushable(l), Pullable(State::Idle(r)))
+}
+
+/// Launches a task which pushes the data of `stream` into a new channel.
+pub fn from_stream<T, S>(stream: S, ctx: Context) -> Pullable<T>
+where
+    T: Sharable + 'static,
+    S: Stream<Item = T::T> + Send + 'static,
+{
+    let (o0, o1) = channel(ctx);
+    ctx.spawn(move |_| async move {
+        futures::pin_mut!(stream);
+        while let Some(data) = stream.next().await {
+            o0.push_sendable(data).await?;
+        }
+        Control::Finished
+    });
+    o1
 }
 
 impl<T: Sharable> Pushable<T> {
     pub async fn push(&self, data: T, ctx: Context) -> Control<()> {
+        self.push_sendable(data.into_sendable(ctx)).await
+    }
+
+    /// Pushes data which is already in its sendable form.
+    pub async fn push_sendable(&self, data: T::T) -> Control<()> {
         self.0
-            .send(data.into_sendable(ctx))
+            .send(Ok(data))
             .map(|_| Control::Continue(()))
             .unwrap_or(Control::Finished)
     }
+
+    /// Fails the channel with `error`, which is pulled after the data that was pushed before
+    /// it. Returns `Finished` if the error was passed on, and otherwise `Failed`, so that the
+    /// task which pushes into the channel can report the error itself.
+    pub async fn fail(&self, error: Error) -> Control<()> {
+        self.0
+            .send(Err(error.clone()))
+            .map(|_| Control::Finished)
+            .unwrap_or(Control::Failed(error))
+    }
 }
 
 impl<T: Sharable> Pullable<T> {
     pub async fn pull(&mut self, ctx: Context) -> Control<<T::T as DynSendable>::T> {
-        self.1
-            .recv()
-            .await
-            .map(|v| Control::Continue(v.into_sharable(ctx)))
-            .unwrap_or(Control::Finished)
+        let data = self.pull_sendable().await?;
+        Control::Continue(data.into_sharable(ctx))
+    }
+
+    /// Pulls data without converting it out of its sendable form.
+    pub async fn pull_sendable(&mut self) -> Control<T::T> {
+        let data = match &mut self.0 {
+            State::Idle(r) => r.recv().await,
+            State::Polling(recv) => {
+                let (data, r) = recv.await;
+                self.0 = State::Idle(r);
+                data
+            }
+        };
+        control(data)
+    }
+
+    fn poll_pull(&mut self, cx: &mut PollContext) -> Poll<Control<T::T>>
+    where
+        T: 'static,
+    {
+        replace_with::replace_with_or_abort(&mut self.0, |state| match state {
+            State::Idle(mut r) => State::Polling(Box::pin(async move { (r.recv().await, r) })),
+            state => state,
+        });
+        if let State::Polling(recv) = &mut self.0 {
+            if let Poll::Ready((data, r)) = recv.as_mut().poll(cx) {
+                self.0 = State::Idle(r);
+                return Poll::Ready(control(data));
+            }
+        }
+        Poll::Pending
+    }
+
+    /// Launches a task which forwards the data of this pullable into a bounded queue, and
+    /// returns the other end of the queue as a stream. The stream ends when the channel does,
+    /// and yields the error last if the channel failed.
+    pub fn into_stream(mut self, ctx: Context) -> impl Stream<Item = Message<T::T>>
+    where
+        T: 'static,
+    {
+        let (mut tx, rx) = futures::channel::mpsc::channel(100);
+        ctx.spawn(move |_| async move {
+            loop {
+                let data = match self.pull_sendable().await {
+                    Control::Continue(data) => Ok(data),
+                    Control::Finished => break,
+                    Control::Failed(error) => Err(error),
+                };
+                let failed = data.is_err();
+                if tx.send(data).await.is_err() || failed {
+                    break;
+                }
+            }
+            Control::Finished
+        });
+        rx
+    }
+}
+
+fn control<T>(data: std::result::Result<Message<T>, RecvError>) -> Control<T> {
+    match data {
+        Ok(Ok(data)) => Control::Continue(data),
+        Ok(Err(error)) => Control::Failed(error),
+        Err(RecvError::Lagged(n)) => Control::Failed(Error::new(format!(
+            "Puller lagged behind the channel and missed {} elements",
+            n
+        ))),
+        Err(RecvError::Closed) => Control::Finished,
+    }
+}
+
+/// Yields data in its sendable form, since there is no `Context` to convert it with. If the
+/// channel failed, or this puller lagged behind it, then the error is yielded as an `Err`.
+impl<T: Sharable + 'static> Stream for Pullable<T> {
+    type Item = Message<T::T>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut PollContext,
+    ) -> Poll<std::option::Option<Message<T::T>>> {
+        self.get_mut().poll_pull(cx).map(|data| match data {
+            Control::Continue(data) => Some(Ok(data)),
+            Control::Finished => None,
+            Control::Failed(error) => Some(Err(error)),
+        })
+    }
+}
+
+/// Accepts data in its sendable form. Sending never blocks, and fails once all pullers have
+/// been dropped. There is no backpressure: the channel retains at least the last [`CAPACITY`]
+/// elements, and a puller which falls further behind fails with an error when it pulls next.
+impl<T: Sharable> Sink<T::T> for Pushable<T> {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut PollContext) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, data: T::T) -> std::result::Result<(), Self::Error> {
+        self.0
+            .send(Ok(data))
+            .map(|_| ())
+            .map_err(|_| Error::new("All pullers of the channel have been dropped"))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut PollContext) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut PollContext) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
     }
 }
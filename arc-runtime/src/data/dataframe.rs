@@ -1,5 +1,17 @@
+//! Tables of named columns, backed by polars. Data frames are sent between tasks encoded in
+//! the Arrow IPC format.
+
+use crate::data::series::Element;
 use crate::prelude::*;
 
+use polars::prelude::CsvReader;
+use polars::prelude::IpcReader;
+use polars::prelude::IpcWriter;
+use polars::prelude::SerReader;
+use polars::prelude::SerWriter;
+
+use std::io::Cursor;
+
 pub mod sharable {
     use crate::prelude::*;
 
@@ -19,30 +31,180 @@ pub mod sharable {
     }
 }
 
-mod sendable {
+pub mod sendable {
     use crate::prelude::*;
 
     #[derive(Clone, From, Send, Serialize, Deserialize)]
     #[from(forward)]
-    pub struct String(pub ConcreteString);
+    pub struct DataFrame(pub ConcreteDataFrame);
 
-    pub type ConcreteString = Box<str>;
+    /// A data frame encoded in the Arrow IPC format.
+    pub type ConcreteDataFrame = Box<[u8]>;
+}
+
+pub(crate) fn to_ipc(df: &polars::frame::DataFrame) -> Box<[u8]> {
+    let mut buf = std::vec::Vec::new();
+    IpcWriter::new(&mut buf)
+        .finish(&mut df.clone())
+        .unwrap_or_else(|e| panic!("Failed to encode data frame: {}", e));
+    buf.into_boxed_slice()
+}
+
+pub(crate) fn from_ipc(bytes: &[u8]) -> polars::frame::DataFrame {
+    IpcReader::new(Cursor::new(bytes))
+        .finish()
+        .unwrap_or_else(|e| panic!("Failed to decode data frame: {}", e))
 }
 
 impl DynSharable for sharable::DataFrame {
-    type T = ();
+    type T = sendable::DataFrame;
     fn into_sendable(&self, ctx: Context) -> Self::T {
-        panic!("DataFrame is not sendable")
+        to_ipc(self.frame()).into()
+    }
+}
+
+impl DynSendable for sendable::DataFrame {
+    type T = sharable::DataFrame;
+    fn into_sharable(&self, ctx: Context) -> Self::T {
+        sharable::ConcreteDataFrame(from_ipc(&self.0)).alloc(ctx)
     }
 }
 
 pub use sharable::DataFrame;
 
+impl Interpolate for DataFrame {}
+
 impl DataFrame {
-    pub fn new(ctx: Context) -> Self {
-        sharable::ConcreteDataFrame(
-            polars::frame::DataFrame::new::<polars::series::Series>(vec![]).unwrap(),
-        )
-        .alloc(ctx)
+    fn wrap(df: polars::frame::DataFrame, ctx: Context) -> DataFrame {
+        sharable::ConcreteDataFrame(df).alloc(ctx)
+    }
+
+    pub(crate) fn frame(&self) -> &polars::frame::DataFrame {
+        &self.0 .0
+    }
+}
+
+fn names(names: Vec<String>) -> std::vec::Vec<std::string::String> {
+    names.0.iter().map(|s| s.as_str().to_string()).collect()
+}
+
+#[rewrite]
+impl DataFrame {
+    pub fn new(ctx: Context) -> DataFrame {
+        let df = polars::frame::DataFrame::new::<polars::series::Series>(vec![]).unwrap();
+        DataFrame::wrap(df, ctx)
+    }
+
+    /// Builds a data frame from columns of equal length.
+    pub fn from_series(columns: Vec<Series>, ctx: Context) -> DataFrame {
+        let columns = columns
+            .0
+            .iter()
+            .map(|s| s.series().clone())
+            .collect::<std::vec::Vec<_>>();
+        let df = polars::frame::DataFrame::new(columns)
+            .unwrap_or_else(|e| panic!("Failed to create data frame: {}", e));
+        DataFrame::wrap(df, ctx)
+    }
+
+    /// Reads a CSV file whose first line is a header of column names, or returns the reason
+    /// why it could not be read.
+    pub fn read_csv(path: String, ctx: Context) -> Result<DataFrame, String> {
+        let path: &str = &path.0;
+        CsvReader::from_path(path)
+            .and_then(|r| r.has_header(true).finish())
+            .map(|df| DataFrame::wrap(df, ctx))
+            .map_err(|e| String::from_str(&format!("Failed to read {:?}: {}", path, e), ctx))
+            .alloc(ctx)
+    }
+
+    pub fn height(self, ctx: Context) -> usize {
+        self.frame().height()
+    }
+
+    /// Returns the column called `name`, or `OptionNone` if there is no such column.
+    pub fn column(self, name: String, ctx: Context) -> Option<Series> {
+        let name: &str = &name.0;
+        self.frame()
+            .column(name)
+            .ok()
+            .map(|column| Series::wrap(column.clone(), ctx))
+            .alloc(ctx)
+    }
+
+    /// Returns the values of the column called `name`, or `OptionNone` if there is no such
+    /// column.
+    pub fn to_vec<T: Element>(self, name: String, ctx: Context) -> Option<Vec<T>> {
+        let name: &str = &name.0;
+        self.frame()
+            .column(name)
+            .ok()
+            .map(|column| Vec::from_iter(T::from_series(column), ctx))
+            .alloc(ctx)
+    }
+
+    pub fn select(self, columns: Vec<String>, ctx: Context) -> DataFrame {
+        let df = self
+            .frame()
+            .select(names(columns))
+            .unwrap_or_else(|e| panic!("Failed to select columns: {}", e));
+        DataFrame::wrap(df, ctx)
+    }
+
+    /// Keeps the rows where `mask`, a boolean series, is true.
+    pub fn filter(self, mask: Series, ctx: Context) -> DataFrame {
+        let df = mask
+            .series()
+            .bool()
+            .and_then(|mask| self.frame().filter(mask))
+            .unwrap_or_else(|e| panic!("Failed to filter data frame: {}", e));
+        DataFrame::wrap(df, ctx)
+    }
+
+    /// Groups rows by the `keys` columns and aggregates `column` in each group. The aggregation
+    /// is one of `count`, `sum`, `mean`, `min`, `max`, `first` and `last`.
+    pub fn group_by_agg(
+        self,
+        keys: Vec<String>,
+        column: String,
+        agg: String,
+        ctx: Context,
+    ) -> DataFrame {
+        let (column, agg): (&str, &str) = (&column.0, &agg.0);
+        let df = self
+            .frame()
+            .groupby(names(keys))
+            .and_then(|g| g.agg(&[(column, &[agg])]))
+            .unwrap_or_else(|e| panic!("Failed to aggregate {:?} by {:?}: {}", column, agg, e));
+        DataFrame::wrap(df, ctx)
+    }
+
+    /// Joins the rows of two data frames where `left_on` equals `right_on`.
+    pub fn join(
+        self,
+        other: DataFrame,
+        left_on: String,
+        right_on: String,
+        ctx: Context,
+    ) -> DataFrame {
+        let (left_on, right_on): (&str, &str) = (&left_on.0, &right_on.0);
+        let df = self
+            .frame()
+            .inner_join(other.frame(), left_on, right_on)
+            .unwrap_or_else(|e| panic!("Failed to join on {:?} = {:?}: {}", left_on, right_on, e));
+        DataFrame::wrap(df, ctx)
+    }
+
+    pub fn sort(self, column: String, descending: bool, ctx: Context) -> DataFrame {
+        let column: &str = &column.0;
+        let df = self
+            .frame()
+            .sort(column, descending)
+            .unwrap_or_else(|e| panic!("Failed to sort by {:?}: {}", column, e));
+        DataFrame::wrap(df, ctx)
+    }
+
+    pub fn head(self, n: usize, ctx: Context) -> DataFrame {
+        DataFrame::wrap(self.frame().head(Some(n)), ctx)
     }
 }
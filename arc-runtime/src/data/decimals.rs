@@ -0,0 +1,192 @@
+//! Exact decimal numbers, backed by `rust_decimal`. A decimal has a 96-bit mantissa and a scale
+//! of up to 28 digits, and is sent as its 16-byte serialized form.
+
+use crate::prelude::*;
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::prelude::ToPrimitive;
+
+pub mod sharable {
+    use crate::prelude::*;
+
+    #[derive(Clone, From, Deref, Debug, Collectable, Finalize, Send, Sync, Unpin, NoTrace)]
+    #[from(forward)]
+    pub struct Decimal(pub Gc<ConcreteDecimal>);
+
+    #[derive(Debug, Collectable, NoTrace, Finalize, Send, Sync, Unpin)]
+    pub struct ConcreteDecimal(pub rust_decimal::Decimal);
+
+    impl Alloc<Decimal> for ConcreteDecimal {
+        fn alloc(self, ctx: Context) -> Decimal {
+            Decimal(ctx.mutator().allocate(self, AllocationSpace::New).into())
+        }
+    }
+
+    /// Decimals are compared and hashed by value, so `1.0` equals `1.00`.
+    impl PartialEq for Decimal {
+        fn eq(&self, other: &Self) -> bool {
+            self.value() == other.value()
+        }
+    }
+
+    impl Eq for Decimal {}
+
+    impl Hash for Decimal {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.value().hash(state)
+        }
+    }
+
+    impl PartialOrd for Decimal {
+        fn partial_cmp(&self, other: &Self) -> std::option::Option<std::cmp::Ordering> {
+            Some(Ord::cmp(self, other))
+        }
+    }
+
+    impl Ord for Decimal {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.value().cmp(other.value())
+        }
+    }
+}
+
+pub mod sendable {
+    use crate::prelude::*;
+
+    #[derive(Clone, From, Send, Serialize, Deserialize)]
+    #[from(forward)]
+    pub struct Decimal(pub ConcreteDecimal);
+
+    pub type ConcreteDecimal = [u8; 16];
+}
+
+impl DynSharable for sharable::Decimal {
+    type T = sendable::Decimal;
+    fn into_sendable(&self, ctx: Context) -> Self::T {
+        self.value().serialize().into()
+    }
+    fn hollow(ctx: Context) -> std::option::Option<Self> {
+        Some(Decimal::from_i64(0, ctx))
+    }
+}
+
+impl DynSendable for sendable::Decimal {
+    type T = sharable::Decimal;
+    fn into_sharable(&self, ctx: Context) -> Self::T {
+        Decimal::wrap(rust_decimal::Decimal::deserialize(self.0), ctx)
+    }
+}
+
+pub use sharable::Decimal;
+
+impl Interpolate for Decimal {
+    fn display(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.value(), f)
+    }
+}
+
+impl Decimal {
+    pub(crate) fn wrap(value: rust_decimal::Decimal, ctx: Context) -> Decimal {
+        sharable::ConcreteDecimal(value).alloc(ctx)
+    }
+
+    pub(crate) fn value(&self) -> &rust_decimal::Decimal {
+        &self.0 .0
+    }
+
+    fn checked(value: std::option::Option<rust_decimal::Decimal>, ctx: Context) -> Option<Decimal> {
+        value.map(|v| Decimal::wrap(v, ctx)).alloc(ctx)
+    }
+}
+
+#[rewrite]
+impl Decimal {
+    pub fn from_i64(v: i64, ctx: Context) -> Decimal {
+        Decimal::wrap(v.into(), ctx)
+    }
+
+    /// Returns the decimal closest to `v`, or `OptionNone` if `v` is not finite or out of range.
+    pub fn from_f64(v: f64, ctx: Context) -> Option<Decimal> {
+        rust_decimal::Decimal::from_f64(v)
+            .map(|v| Decimal::wrap(v, ctx))
+            .alloc(ctx)
+    }
+
+    /// Parses a decimal such as `-12.50`. Returns `OptionNone` if `s` is not a decimal, or if it
+    /// has more digits than fit in a decimal.
+    pub fn parse(s: String, ctx: Context) -> Option<Decimal> {
+        let s: &str = &s.0;
+        s.parse().ok().map(|v| Decimal::wrap(v, ctx)).alloc(ctx)
+    }
+
+    pub fn to_string(self, ctx: Context) -> String {
+        String::from_str(&self.value().to_string(), ctx)
+    }
+
+    pub fn to_f64(self, ctx: Context) -> f64 {
+        self.value().to_f64().unwrap()
+    }
+
+    /// The number of digits after the decimal point.
+    pub fn scale(self, ctx: Context) -> u32 {
+        self.value().scale()
+    }
+
+    /// Rounds to `dp` digits after the decimal point, rounding half to even.
+    pub fn round(self, dp: u32, ctx: Context) -> Decimal {
+        Decimal::wrap(self.value().round_dp(dp), ctx)
+    }
+
+    /// Adds `other` to `self`, or returns `OptionNone` if the sum overflows.
+    pub fn add(self, other: Decimal, ctx: Context) -> Option<Decimal> {
+        Decimal::checked(self.value().checked_add(*other.value()), ctx)
+    }
+
+    /// Subtracts `other` from `self`, or returns `OptionNone` if the difference overflows.
+    pub fn sub(self, other: Decimal, ctx: Context) -> Option<Decimal> {
+        Decimal::checked(self.value().checked_sub(*other.value()), ctx)
+    }
+
+    /// Multiplies `self` by `other`, or returns `OptionNone` if the product overflows.
+    pub fn mul(self, other: Decimal, ctx: Context) -> Option<Decimal> {
+        Decimal::checked(self.value().checked_mul(*other.value()), ctx)
+    }
+
+    /// Divides `self` by `other`, or returns `OptionNone` if `other` is zero or the quotient
+    /// overflows.
+    pub fn div(self, other: Decimal, ctx: Context) -> Option<Decimal> {
+        Decimal::checked(self.value().checked_div(*other.value()), ctx)
+    }
+
+    pub fn neg(self, ctx: Context) -> Decimal {
+        Decimal::wrap(-*self.value(), ctx)
+    }
+
+    pub fn abs(self, ctx: Context) -> Decimal {
+        Decimal::wrap(self.value().abs(), ctx)
+    }
+
+    pub fn eq(self, other: Decimal, ctx: Context) -> bool {
+        self == other
+    }
+
+    pub fn neq(self, other: Decimal, ctx: Context) -> bool {
+        self != other
+    }
+
+    pub fn lt(self, other: Decimal, ctx: Context) -> bool {
+        self < other
+    }
+
+    pub fn lt_eq(self, other: Decimal, ctx: Context) -> bool {
+        self <= other
+    }
+
+    pub fn gt(self, other: Decimal, ctx: Context) -> bool {
+        self > other
+    }
+
+    pub fn gt_eq(self, other: Decimal, ctx: Context) -> bool {
+        self >= other
+    }
+}
@@ -0,0 +1,347 @@
+//! Values whose type is only known at runtime, for existential types such as type class
+//! instances and row-polymorphic records.
+//!
+//! An erased value remembers the tag of its type and can only be unerased as that type. Its
+//! sendable form carries the tag and the value serialized as JSON. A value is decoded by a
+//! decoder which is registered when a value of the same type is first erased, or explicitly
+//! through [`Erased::register`]. A value whose tag has no decoder stays encoded until it is
+//! unerased, and can still be sent on.
+
+use crate::prelude::*;
+
+use std::any::type_name;
+use std::any::Any;
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::RwLock;
+
+/// A type whose values can be erased. Its tag identifies the type on the wire, so it must be
+/// the same in every build which exchanges values of the type, and differ from the tags of all
+/// other types. The [`type_name`] of a type is not guaranteed to be either.
+pub trait Tagged: 'static {
+    fn tag() -> std::string::String;
+}
+
+/// Returns the tag of a type named `name` which is applied to type arguments with the tags
+/// `args`.
+pub fn tag_of(name: &str, args: &[std::string::String]) -> std::string::String {
+    if args.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}<{}>", name, args.join(","))
+    }
+}
+
+/// Returns the tag of `T`, which is computed once per type.
+fn tag<T: Tagged + 'static>() -> &'static str {
+    static TAGS: RwLock<BTreeMap<TypeId, &'static str>> = RwLock::new(BTreeMap::new());
+    let id = TypeId::of::<T>();
+    if let Some(tag) = TAGS.read().unwrap().get(&id).copied() {
+        return tag;
+    }
+    *TAGS
+        .write()
+        .unwrap()
+        .entry(id)
+        .or_insert_with(|| &*Box::leak(T::tag().into_boxed_str()))
+}
+
+macro_rules! impl_tagged {
+    ($($ty:ty => $tag:literal),* $(,)?) => {
+        $(
+            impl Tagged for $ty {
+                fn tag() -> std::string::String {
+                    $tag.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_tagged!(
+    i8 => "i8",
+    i16 => "i16",
+    i32 => "i32",
+    i64 => "i64",
+    i128 => "i128",
+    u8 => "u8",
+    u16 => "u16",
+    u32 => "u32",
+    u64 => "u64",
+    u128 => "u128",
+    usize => "usize",
+    f32 => "f32",
+    f64 => "f64",
+    bool => "bool",
+    char => "char",
+    unit => "unit",
+    String => "String",
+    Erased => "Erased",
+    DateTime => "DateTime",
+    Duration => "Duration",
+    Bytes => "Bytes",
+    Decimal => "Decimal",
+    BigInt => "BigInt",
+);
+
+#[cfg(feature = "dataframes")]
+impl_tagged!(
+    crate::data::series::Series => "Series",
+    crate::data::dataframe::DataFrame => "DataFrame",
+);
+
+impl<T: Sharable + Tagged> Tagged for Vec<T> {
+    fn tag() -> std::string::String {
+        tag_of("Vec", &[T::tag()])
+    }
+}
+
+impl<T: Sharable + Tagged> Tagged for Cell<T> {
+    fn tag() -> std::string::String {
+        tag_of("Cell", &[T::tag()])
+    }
+}
+
+impl<T: Sharable + Hash + Eq + Tagged> Tagged for Set<T> {
+    fn tag() -> std::string::String {
+        tag_of("Set", &[T::tag()])
+    }
+}
+
+impl<K: Sharable + Hash + Eq + Tagged, V: Sharable + Tagged> Tagged for Map<K, V> {
+    fn tag() -> std::string::String {
+        tag_of("Map", &[K::tag(), V::tag()])
+    }
+}
+
+/// A value of some sharable type, which can be traced and sent without knowing the type.
+pub trait Value: dyn_clone::DynClone + Debug + AsyncSafe + Any {
+    fn trace(&mut self, vis: &mut dyn Visitor);
+    fn encode(&self, ctx: Context) -> std::vec::Vec<u8>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+dyn_clone::clone_trait_object!(Value);
+
+impl<T> Value for T
+where
+    T: Sharable + 'static,
+{
+    fn trace(&mut self, vis: &mut dyn Visitor) {
+        Trace::trace(self, vis);
+    }
+
+    fn encode(&self, ctx: Context) -> std::vec::Vec<u8> {
+        // The value is encoded on its own, and not as part of the graph it is erased within.
+        crate::data::graphs::detached(|| serde_json::to_vec(&self.into_sendable(ctx)))
+            .unwrap_or_else(|e| panic!("Failed to encode erased {}: {}", type_name::<T>(), e))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub mod sharable {
+    use crate::prelude::*;
+    use std::borrow::Cow;
+
+    #[derive(Clone, From, Deref, Debug, Collectable, Finalize, Send, Sync, Unpin, Trace)]
+    pub struct Erased(pub Gc<ConcreteErased>);
+
+    #[derive(Clone, Debug, Collectable, Finalize)]
+    pub struct ConcreteErased {
+        pub tag: Cow<'static, str>,
+        pub contents: Contents,
+    }
+
+    #[derive(Clone, Debug)]
+    pub enum Contents {
+        Decoded(Box<dyn super::Value>),
+        /// The payload of a value which was received without a decoder for its tag.
+        Encoded(Box<[u8]>),
+    }
+
+    unsafe impl Trace for ConcreteErased {
+        fn trace(&mut self, vis: &mut dyn Visitor) {
+            if let Contents::Decoded(value) = &mut self.contents {
+                super::Value::trace(&mut **value, vis);
+            }
+        }
+    }
+
+    impl Alloc<Erased> for ConcreteErased {
+        fn alloc(self, ctx: Context) -> Erased {
+            Erased(ctx.mutator().allocate(self, AllocationSpace::New).into())
+        }
+    }
+}
+
+pub mod sendable {
+    use crate::prelude::*;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct Erased {
+        pub tag: std::string::String,
+        pub payload: Box<[u8]>,
+    }
+}
+
+impl DynSharable for sharable::Erased {
+    type T = sendable::Erased;
+    fn into_sendable(&self, ctx: Context) -> Self::T {
+        let payload = match &self.contents {
+            Contents::Decoded(value) => value.encode(ctx).into_boxed_slice(),
+            Contents::Encoded(payload) => payload.clone(),
+        };
+        sendable::Erased {
+            tag: self.tag.to_string(),
+            payload,
+        }
+    }
+}
+
+impl DynSendable for sendable::Erased {
+    type T = sharable::Erased;
+    fn into_sharable(&self, ctx: Context) -> Self::T {
+        let decoder = DECODERS.read().unwrap().get(self.tag.as_str()).copied();
+        match decoder.map(|decoder| decoder(&self.payload, ctx)) {
+            Some(Ok(erased)) => erased,
+            _ => sharable::ConcreteErased {
+                tag: Cow::Owned(self.tag.clone()),
+                contents: Contents::Encoded(self.payload.clone()),
+            }
+            .alloc(ctx),
+        }
+    }
+}
+
+pub use sharable::Contents;
+pub use sharable::Erased;
+
+impl Interpolate for Erased {}
+
+type Decoder = fn(&[u8], Context) -> serde_json::Result<Erased>;
+
+static DECODERS: RwLock<BTreeMap<&'static str, Decoder>> = RwLock::new(BTreeMap::new());
+
+fn decode<T>(payload: &[u8], ctx: Context) -> serde_json::Result<T>
+where
+    T: Sharable + 'static,
+    T::T: DynSendable<T = T>,
+{
+    let value = serde_json::from_slice::<T::T>(payload)?;
+    Ok(crate::data::graphs::detached(|| value.into_sharable(ctx)))
+}
+
+fn decode_erased<T>(payload: &[u8], ctx: Context) -> serde_json::Result<Erased>
+where
+    T: Sharable + Tagged + 'static,
+    T::T: DynSendable<T = T>,
+{
+    decode::<T>(payload, ctx).map(|value| Erased::erase(value, ctx))
+}
+
+/// The error of unerasing a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UneraseError {
+    /// The value has another type than the one it is unerased as.
+    TypeMismatch(TypeMismatch),
+    /// The value was received without a decoder, and its payload could not be decoded.
+    Decode {
+        tag: std::string::String,
+        message: std::string::String,
+    },
+}
+
+/// The tags of a value and of the type it is unerased as.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeMismatch {
+    pub expected: &'static str,
+    pub found: std::string::String,
+}
+
+impl fmt::Display for UneraseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UneraseError::TypeMismatch(TypeMismatch { expected, found }) => {
+                write!(
+                    f,
+                    "Cannot unerase a value of type {} as {}",
+                    found, expected
+                )
+            }
+            UneraseError::Decode { tag, message } => {
+                write!(f, "Failed to decode erased {}: {}", tag, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UneraseError {}
+
+impl Erased {
+    /// Registers a decoder for erased values of type `T`, so that they are decoded when they
+    /// are received by a process which has not erased a value of that type itself.
+    pub fn register<T>()
+    where
+        T: Sharable + Tagged + 'static,
+        T::T: DynSendable<T = T>,
+    {
+        let tag = tag::<T>();
+        if !DECODERS.read().unwrap().contains_key(tag) {
+            DECODERS.write().unwrap().insert(tag, decode_erased::<T>);
+        }
+    }
+
+    pub fn erase<T>(value: T, ctx: Context) -> Erased
+    where
+        T: Sharable + Tagged + 'static,
+        T::T: DynSendable<T = T>,
+    {
+        Erased::register::<T>();
+        sharable::ConcreteErased {
+            tag: Cow::Borrowed(tag::<T>()),
+            contents: Contents::Decoded(Box::new(value)),
+        }
+        .alloc(ctx)
+    }
+
+    /// Returns the value as a `T`, decoding it first if it was received without a decoder.
+    pub fn unerase<T>(self, ctx: Context) -> std::result::Result<T, UneraseError>
+    where
+        T: Sharable + Tagged + 'static,
+        T::T: DynSendable<T = T>,
+    {
+        let mismatch = || {
+            UneraseError::TypeMismatch(TypeMismatch {
+                expected: tag::<T>(),
+                found: self.tag.to_string(),
+            })
+        };
+        match &self.contents {
+            Contents::Decoded(value) => value
+                .as_any()
+                .downcast_ref::<T>()
+                .cloned()
+                .ok_or_else(mismatch),
+            Contents::Encoded(_) if self.tag != tag::<T>() => Err(mismatch()),
+            Contents::Encoded(payload) => {
+                decode::<T>(payload, ctx).map_err(|e| UneraseError::Decode {
+                    tag: self.tag.to_string(),
+                    message: e.to_string(),
+                })
+            }
+        }
+    }
+
+    /// Returns `true` if the erased value has type `T`.
+    pub fn is<T: Sharable + Tagged + 'static>(self, ctx: Context) -> bool {
+        match &self.contents {
+            Contents::Decoded(value) => value.as_any().is::<T>(),
+            Contents::Encoded(_) => self.tag == tag::<T>(),
+        }
+    }
+}
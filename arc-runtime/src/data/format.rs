@@ -0,0 +1,208 @@
+//! Runtime support for string interpolation.
+//!
+//! A template is rendered by [`format`], which takes the arguments as trait objects so that
+//! values of any type can be mixed. Placeholders are written `{}` or `{:spec}`, where `spec` is
+//! `[<^>][0][width][.precision][?]`, and `{{` and `}}` escape braces. A `?` selects the debug
+//! representation, which every sharable type has.
+
+use crate::data::cells::Cell;
+use crate::data::maps::Map;
+use crate::data::sets::Set;
+use crate::prelude::*;
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Write;
+
+/// A value which can be interpolated into a string. Types without a natural textual form are
+/// displayed by their debug representation.
+pub trait Interpolate: Debug {
+    fn display(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+macro_rules! impl_interpolate_display {
+    ($($ty:ty),*) => {
+        $(
+            impl Interpolate for $ty {
+                fn display(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    Display::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_interpolate_display!(
+    i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize, f32, f64, bool, char
+);
+
+impl Interpolate for () {}
+
+impl Interpolate for String {
+    fn display(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s: &str = &self.0;
+        Display::fmt(s, f)
+    }
+}
+
+impl Interpolate for DateTime {
+    fn display(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Interpolate for Duration {
+    fn display(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<T: Sharable> Interpolate for Vec<T> {}
+impl<T: Sharable> Interpolate for Cell<T> {}
+impl<K: Sharable + Hash + Eq, V: Sharable> Interpolate for Map<K, V> {}
+impl<T: Sharable + Hash + Eq> Interpolate for Set<T> {}
+
+macro_rules! impl_interpolate_tuple {
+    ($($t:ident),+) => {
+        impl<$($t: Debug),+> Interpolate for ($($t,)+) {}
+    };
+}
+
+impl_interpolate_tuple!(A0);
+impl_interpolate_tuple!(A0, A1);
+impl_interpolate_tuple!(A0, A1, A2);
+impl_interpolate_tuple!(A0, A1, A2, A3);
+impl_interpolate_tuple!(A0, A1, A2, A3, A4);
+impl_interpolate_tuple!(A0, A1, A2, A3, A4, A5);
+impl_interpolate_tuple!(A0, A1, A2, A3, A4, A5, A6);
+impl_interpolate_tuple!(A0, A1, A2, A3, A4, A5, A6, A7);
+impl_interpolate_tuple!(A0, A1, A2, A3, A4, A5, A6, A7, A8);
+impl_interpolate_tuple!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_interpolate_tuple!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_interpolate_tuple!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+
+#[derive(Clone, Copy)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Default)]
+struct Spec {
+    align: std::option::Option<Align>,
+    zero: bool,
+    width: usize,
+    precision: std::option::Option<usize>,
+    debug: bool,
+}
+
+impl Spec {
+    fn parse(s: &str) -> std::option::Option<Spec> {
+        let mut spec = Spec::default();
+        let mut s = s;
+        let align = match s.chars().next() {
+            Some('<') => Some(Align::Left),
+            Some('^') => Some(Align::Center),
+            Some('>') => Some(Align::Right),
+            _ => None,
+        };
+        if align.is_some() {
+            spec.align = align;
+            s = &s[1..];
+        }
+        if let Some(rest) = s.strip_prefix('0') {
+            spec.zero = true;
+            s = rest;
+        }
+        if let Some(rest) = s.strip_suffix('?') {
+            spec.debug = true;
+            s = rest;
+        }
+        let (width, precision) = match s.split_once('.') {
+            Some((width, precision)) => (width, Some(precision.parse().ok()?)),
+            None => (s, None),
+        };
+        if !width.is_empty() {
+            spec.width = width.parse().ok()?;
+        }
+        spec.precision = precision;
+        Some(spec)
+    }
+}
+
+/// Displays an argument as selected by a placeholder, so that the standard formatting
+/// machinery can apply width and precision to it.
+struct Arg<'a> {
+    value: &'a dyn Interpolate,
+    debug: bool,
+}
+
+impl Display for Arg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.debug {
+            Debug::fmt(self.value, f)
+        } else {
+            self.value.display(f)
+        }
+    }
+}
+
+fn render(out: &mut std::string::String, arg: Arg, spec: &Spec) -> fmt::Result {
+    let w = spec.width;
+    match (spec.align, spec.zero, spec.precision) {
+        (None, false, None) => write!(out, "{:w$}", arg),
+        (None, false, Some(p)) => write!(out, "{:w$.p$}", arg),
+        (None, true, None) => write!(out, "{:0w$}", arg),
+        (None, true, Some(p)) => write!(out, "{:0w$.p$}", arg),
+        (Some(Align::Left), _, None) => write!(out, "{:<w$}", arg),
+        (Some(Align::Left), _, Some(p)) => write!(out, "{:<w$.p$}", arg),
+        (Some(Align::Center), _, None) => write!(out, "{:^w$}", arg),
+        (Some(Align::Center), _, Some(p)) => write!(out, "{:^w$.p$}", arg),
+        (Some(Align::Right), _, None) => write!(out, "{:>w$}", arg),
+        (Some(Align::Right), _, Some(p)) => write!(out, "{:>w$.p$}", arg),
+    }
+}
+
+/// Renders `template` with `args` into a single newly allocated string.
+pub fn format(template: &str, args: &[&dyn Interpolate], ctx: Context) -> String {
+    let mut out = std::string::String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(i) = rest.find(|c| c == '{' || c == '}') {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let end = match (tail.starts_with('{'), tail.find('}')) {
+            (true, Some(end)) => end,
+            _ => panic!("Unbalanced braces in template {:?}", template),
+        };
+        let placeholder = &tail[1..end];
+        let spec = match placeholder.strip_prefix(':') {
+            Some(spec) => Spec::parse(spec),
+            None if placeholder.is_empty() => Some(Spec::default()),
+            None => None,
+        }
+        .unwrap_or_else(|| panic!("Invalid placeholder {{{}}} in {:?}", placeholder, template));
+        let value = *args
+            .next()
+            .unwrap_or_else(|| panic!("Too few arguments for template {:?}", template));
+        let arg = Arg {
+            value,
+            debug: spec.debug,
+        };
+        render(&mut out, arg, &spec).expect("Failed to format argument");
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    if args.next().is_some() {
+        panic!("Too many arguments for template {:?}", template);
+    }
+    String::from_str(&out, ctx)
+}
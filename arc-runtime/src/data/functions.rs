@@ -1,29 +1,289 @@
 use crate::context::Context;
+use crate::data::erased::tag_of;
+use crate::data::erased::Erased;
+use crate::data::erased::Tagged;
+use crate::data::AsyncSafe;
 use crate::data::DynSharable;
 use comet::api::Collectable;
 use comet::api::Finalize;
 use comet::api::Trace;
-use std::fmt::Debug;
+use std::any::type_name;
+use std::any::Any;
+use std::fmt;
 use std::ptr::NonNull;
 
+/// A function value which can be called indirectly, e.g., by a builtin operator.
+///
+/// `I` is the tuple of arguments, following the calling convention of `call_indirect!`.
+pub trait Call<I, O>: Clone + AsyncSafe {
+    fn call(&self, input: I, ctx: Context) -> O;
+}
+
+impl<I, O> Call<I, O> for fn(I, Context) -> O {
+    fn call(&self, input: I, ctx: Context) -> O {
+        self(input, ctx)
+    }
+}
+
+/// The code of a function value. A closure pairs its code with the environment it captured,
+/// which is passed to the code as an extra first argument on every call.
+pub enum Code<I: 'static, O: 'static> {
+    Function(fn(I, Context) -> O),
+    Closure(fn(Erased, I, Context) -> O, Erased),
+}
+
+impl<I, O> Clone for Code<I, O> {
+    fn clone(&self) -> Self {
+        match self {
+            Code::Function(ptr) => Code::Function(*ptr),
+            Code::Closure(ptr, env) => Code::Closure(*ptr, env.clone()),
+        }
+    }
+}
+
+impl<I, O> Code<I, O> {
+    pub fn call(&self, input: I, ctx: Context) -> O {
+        match self {
+            Code::Function(ptr) => ptr(input, ctx),
+            Code::Closure(ptr, env) => ptr(env.clone(), input, ctx),
+        }
+    }
+}
+
+/// The code of a function value in its sendable form. The code is resolved when the value is
+/// received, so that a value which cannot be resolved fails to decode.
+pub enum SendableCode<I: 'static, O: 'static> {
+    Function(fn(I, Context) -> O),
+    Closure(fn(Erased, I, Context) -> O, <Erased as DynSharable>::T),
+}
+
+impl<I, O> Clone for SendableCode<I, O> {
+    fn clone(&self) -> Self {
+        match self {
+            SendableCode::Function(ptr) => SendableCode::Function(*ptr),
+            SendableCode::Closure(ptr, env) => SendableCode::Closure(*ptr, env.clone()),
+        }
+    }
+}
+
+/// The wire format of a function value: its tag, and the environment if it is a closure.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Wire<T, E> {
+    pub tag: T,
+    pub env: std::option::Option<E>,
+}
+
+pub fn serialize_closure_error<E: serde::ser::Error>() -> E {
+    E::custom("A closure must be converted to its sendable form to be serialized")
+}
+
+pub fn resolve_error<E: serde::de::Error>(error: ResolveError) -> E {
+    E::custom(error)
+}
+
+/// The error of resolving a function value from its name or tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResolveError {
+    /// No function with the name is declared.
+    UnknownFunction(std::string::String),
+    /// The function is a closure, which cannot be resolved without its environment.
+    Closure(&'static str),
+    /// The function is not a closure, but was sent with an environment.
+    Environment(&'static str),
+    /// The function does not have the signature of the function value.
+    SignatureMismatch {
+        path: &'static str,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The function was declared with a different path or signature by the peer.
+    VersionMismatch {
+        path: &'static str,
+        local: u64,
+        remote: u64,
+    },
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResolveError::UnknownFunction(name) => write!(f, "Unknown function {}", name),
+            ResolveError::Closure(name) => {
+                write!(f, "Cannot resolve closure {} without its environment", name)
+            }
+            ResolveError::Environment(name) => {
+                write!(f, "Function {} does not capture an environment", name)
+            }
+            ResolveError::SignatureMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Function {} has signature {}, expected {}",
+                path, found, expected
+            ),
+            ResolveError::VersionMismatch {
+                path,
+                local,
+                remote,
+            } => write!(
+                f,
+                "Function {} has version {:#x} but the peer sent version {:#x}, \
+                 the two processes must be built from the same function declarations",
+                path, local, remote
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+/// Returns the signature of a function with input `I` and output `O`, which is spelled with
+/// the tags of the types so that it is the same in every build.
+pub fn signature<I: Tagged, O: Tagged>() -> std::string::String {
+    tag_of("fn", &[I::tag(), O::tag()])
+}
+
+/// Returns a hash of the path and [`signature`] of a function with input `I` and output `O`.
+/// The hash uses FNV-1a so that it is the same in every process built from the same source.
+pub fn version<I: Tagged, O: Tagged>(path: &str) -> u64 {
+    let signature = signature::<I, O>();
+    path.bytes()
+        .chain(std::iter::once(0))
+        .chain(signature.bytes())
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+/// Casts the code of the function at `path` to the pointer type `T`, if it has that type.
+pub fn cast<F: Any, T: Any + Copy>(path: &'static str, code: F) -> Result<T, ResolveError> {
+    (&code as &dyn Any)
+        .downcast_ref::<T>()
+        .copied()
+        .ok_or(ResolveError::SignatureMismatch {
+            path,
+            expected: type_name::<T>(),
+            found: type_name::<F>(),
+        })
+}
+
+/// Declares the functions which can be used as function values. Functions are listed first,
+/// followed by the code of closures, which takes the captured environment as its first argument:
+///
+/// ```ignore
+/// declare_functions!(f, g; closures: h);
+/// ```
+///
+/// A function value is sent as its tag together with the [`version`] of the function, which is
+/// checked by the receiver. Declared functions can also be resolved by name through
+/// `Function::resolve`.
 #[macro_export]
 macro_rules! declare_functions {
-    ($($id:ident),* $(,)?) => {
-        #[derive(Send, Sync, Unpin, Collectable, Finalize, NoTrace)]
+    ($($id:ident),* $(,)? $(; closures: $($cid:ident),* $(,)?)?) => {
+        #[derive(Send, Sync, Unpin, Collectable, Finalize)]
         pub struct Function<I: 'static, O: 'static> {
-            pub ptr: fn(I, Context) -> O,
+            pub code: Code<I, O>,
             pub tag: FunctionTag<I, O>,
         }
-        #[derive(Debug, Copy, Send, Sync, Unpin, Serialize, Deserialize)]
+        #[derive(Debug, Copy, Send, Sync, Unpin)]
         pub struct FunctionTag<I, O>(pub Tag, pub std::marker::PhantomData<(I, O)>);
-        #[derive(Debug, Clone, Copy, Send, Serialize, Deserialize)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Send, Serialize, Deserialize)]
         #[allow(non_camel_case_types)]
         pub enum Tag {
             $($id,)*
+            $($($cid,)*)?
+        }
+        /// The sendable form of a function value, which carries the environment of a closure.
+        #[derive(Send, Sync, Unpin)]
+        pub struct SendableFunction<I: 'static, O: 'static> {
+            pub tag: FunctionTag<I, O>,
+            pub code: SendableCode<I, O>,
+        }
+        impl Tag {
+            /// The tags of all declared functions and closures.
+            pub const ALL: &'static [Tag] = &[$(Tag::$id,)* $($(Tag::$cid,)*)?];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Tag::$id => stringify!($id),)*
+                    $($(Tag::$cid => stringify!($cid),)*)?
+                }
+            }
+
+            /// The path of the function, e.g., `crate::module::f`.
+            pub fn path(self) -> &'static str {
+                match self {
+                    $(Tag::$id => concat!(module_path!(), "::", stringify!($id)),)*
+                    $($(Tag::$cid => concat!(module_path!(), "::", stringify!($cid)),)*)?
+                }
+            }
+
+            pub fn from_name(name: &str) -> std::option::Option<Tag> {
+                Tag::ALL.iter().copied().find(|tag| tag.name() == name)
+            }
+        }
+        impl<I: 'static, O: 'static> FunctionTag<I, O> {
+            pub fn new(tag: Tag) -> Self {
+                Self(tag, std::marker::PhantomData)
+            }
+        }
+        impl<I: Tagged, O: Tagged> FunctionTag<I, O> {
+            /// A hash of the path and signature of the function, see [`version`].
+            pub fn version(&self) -> u64 {
+                $crate::data::functions::version::<I, O>(self.0.path())
+            }
+        }
+        impl<I: 'static, O: 'static> FunctionTag<I, O> {
+            /// Resolves the code of a declared function, checking that it has the signature of
+            /// the function value.
+            pub fn code(&self) -> std::result::Result<fn(I, Context) -> O, ResolveError> {
+                let tag = self.0;
+                match tag {
+                    $(Tag::$id => {
+                        let ptr: fn(_, Context) -> _ = $id;
+                        $crate::data::functions::cast(tag.path(), ptr)
+                    })*
+                    #[allow(unreachable_patterns)]
+                    tag => Err(ResolveError::Closure(tag.name())),
+                }
+            }
+
+            /// Resolves the code of a declared closure, checking that it has the signature of
+            /// the function value.
+            pub fn closure_code(
+                &self,
+            ) -> std::result::Result<fn(Erased, I, Context) -> O, ResolveError> {
+                let tag = self.0;
+                match tag {
+                    $($(Tag::$cid => {
+                        let ptr: fn(Erased, _, Context) -> _ = $crate::closure_code!($cid);
+                        $crate::data::functions::cast(tag.path(), ptr)
+                    })*)?
+                    #[allow(unreachable_patterns)]
+                    tag => Err(ResolveError::Environment(tag.name())),
+                }
+            }
+        }
+        impl<I: 'static, O: 'static> Function<I, O> {
+            /// Resolves a declared function by its name.
+            pub fn resolve(name: &str) -> std::result::Result<Self, ResolveError> {
+                let tag = Tag::from_name(name)
+                    .ok_or_else(|| ResolveError::UnknownFunction(name.to_string()))?;
+                Self::from_tag(tag)
+            }
+
+            /// Resolves a declared function by its tag, checking that it has the signature of
+            /// the function value.
+            pub fn from_tag(tag: Tag) -> std::result::Result<Self, ResolveError> {
+                let tag = FunctionTag::new(tag);
+                Ok(Function { code: Code::Function(tag.code()?), tag })
+            }
         }
         impl<I, O> Clone for Function<I, O> {
             fn clone(&self) -> Self {
-                Self { ptr: self.ptr.clone(), tag: self.tag.clone() }
+                Self { code: self.code.clone(), tag: self.tag.clone() }
             }
         }
         impl<I, O> std::fmt::Debug for Function<I, O> {
@@ -36,42 +296,118 @@ macro_rules! declare_functions {
                 Self(self.0, std::marker::PhantomData)
             }
         }
-        impl<I, O> DynSharable for Function<I, O> {
-            type T = FunctionTag<I, O>;
+        impl<I, O> Clone for SendableFunction<I, O> {
+            fn clone(&self) -> Self {
+                Self { tag: self.tag.clone(), code: self.code.clone() }
+            }
+        }
+        unsafe impl<I, O> Trace for Function<I, O> {
+            fn trace(&mut self, vis: &mut dyn Visitor) {
+                if let Code::Closure(_, env) = &mut self.code {
+                    env.trace(vis);
+                }
+            }
+        }
+        impl<I, O> Call<I, O> for Function<I, O> {
+            fn call(&self, input: I, ctx: Context) -> O {
+                self.code.call(input, ctx)
+            }
+        }
+        impl<I: Tagged, O: Tagged> DynSharable for Function<I, O> {
+            type T = SendableFunction<I, O>;
             fn into_sendable(&self, ctx: Context) -> Self::T {
-                self.tag.clone()
+                let code = match &self.code {
+                    Code::Function(ptr) => SendableCode::Function(*ptr),
+                    Code::Closure(ptr, env) => SendableCode::Closure(*ptr, env.into_sendable(ctx)),
+                };
+                SendableFunction { tag: self.tag.clone(), code }
+            }
+        }
+        impl<I: Tagged, O: Tagged> Tagged for Function<I, O> {
+            fn tag() -> std::string::String {
+                $crate::data::erased::tag_of("Function", &[I::tag(), O::tag()])
             }
         }
-        impl<I: 'static, O: 'static> DynSendable for FunctionTag<I, O> {
+        impl<I: Tagged, O: Tagged> DynSendable for SendableFunction<I, O> {
             type T = Function<I, O>;
             fn into_sharable(&self, ctx: Context) -> Self::T {
-                unsafe {
-                    match self.0 {
-                        $(Tag::$id => Function {
-                            ptr: std::mem::transmute($id as usize),
-                            tag: self.clone()
-                        }),*
-                    }
-                }
+                let code = match &self.code {
+                    SendableCode::Function(ptr) => Code::Function(*ptr),
+                    SendableCode::Closure(ptr, env) => Code::Closure(*ptr, env.into_sharable(ctx)),
+                };
+                Function { code, tag: self.tag.clone() }
+            }
+        }
+        impl<I: Tagged, O: Tagged> Serialize for SendableFunction<I, O> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                let env = match &self.code {
+                    SendableCode::Function(_) => None,
+                    SendableCode::Closure(_, env) => Some(env),
+                };
+                $crate::data::functions::Wire { tag: &self.tag, env }.serialize(serializer)
+            }
+        }
+        impl<'i, I: Tagged, O: Tagged> Deserialize<'i> for SendableFunction<I, O> {
+            fn deserialize<D: Deserializer<'i>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let $crate::data::functions::Wire { tag, env } =
+                    $crate::data::functions::Wire::<FunctionTag<I, O>, <Erased as DynSharable>::T>::deserialize(deserializer)?;
+                let code = match env {
+                    None => tag.code().map(SendableCode::Function),
+                    Some(env) => tag.closure_code().map(|ptr| SendableCode::Closure(ptr, env)),
+                };
+                code.map(|code| SendableFunction { tag, code })
+                    .map_err($crate::data::functions::resolve_error)
             }
         }
-        impl<I, O> Serialize for Function<I, O> {
-            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-                self.tag.0.serialize(serializer)
+        impl<I: Tagged, O: Tagged> Serialize for FunctionTag<I, O> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                (self.0, self.version()).serialize(serializer)
             }
         }
-        impl<'i, I, O> Deserialize<'i> for Function<I, O> {
-            fn deserialize<D: Deserializer<'i>>(deserializer: D) -> Result<Self, D::Error> {
-                unsafe {
-                    match Tag::deserialize(deserializer)? {
-                        $(Tag::$id => Ok(Function {
-                            ptr: std::mem::transmute($id as usize),
-                            tag: FunctionTag(Tag::$id, std::marker::PhantomData)
-                        }),)*
-                    }
+        impl<'i, I: Tagged, O: Tagged> Deserialize<'i> for FunctionTag<I, O> {
+            fn deserialize<D: Deserializer<'i>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let (tag, remote) = <(Tag, u64)>::deserialize(deserializer)?;
+                let tag = FunctionTag::new(tag);
+                let local = tag.version();
+                if local == remote {
+                    Ok(tag)
+                } else {
+                    Err($crate::data::functions::resolve_error(ResolveError::VersionMismatch {
+                        path: tag.0.path(),
+                        local,
+                        remote,
+                    }))
                 }
             }
         }
+        impl<I: Tagged, O: Tagged> Serialize for Function<I, O> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                match self.code {
+                    Code::Function(_) => self.tag.serialize(serializer),
+                    Code::Closure(..) => Err($crate::data::functions::serialize_closure_error()),
+                }
+            }
+        }
+        impl<'i, I: Tagged, O: Tagged> Deserialize<'i> for Function<I, O> {
+            fn deserialize<D: Deserializer<'i>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let tag = FunctionTag::<I, O>::deserialize(deserializer)?;
+                Function::from_tag(tag.0).map_err($crate::data::functions::resolve_error)
+            }
+        }
+    };
+}
+
+/// The code of a closure, which unerases the environment before calling `$cid`.
+#[macro_export]
+macro_rules! closure_code {
+    ($cid:ident) => {
+        |env: Erased, input, ctx: Context| {
+            $cid(
+                Erased::unerase(env, ctx).unwrap_or_else(|e| panic!("{}", e)),
+                input,
+                ctx,
+            )
+        }
     };
 }
 
@@ -89,8 +425,8 @@ macro_rules! function {
     // Create a function value
     ($fun:ident) => {
         Function {
-            ptr: $fun,
-            tag: FunctionTag(Tag::$fun, std::marker::PhantomData),
+            code: Code::Function($fun),
+            tag: FunctionTag::new(Tag::$fun),
         }
     };
     // Create a function type
@@ -34,6 +34,14 @@ impl<T: Sharable> Gc<T> {
     }
 }
 
+impl<T: Garbage> Gc<T> {
+    /// Must be called after writing a reference into the object, so the collector learns about
+    /// it even if the object itself has already been traced.
+    pub fn write_barrier(&self, ctx: Context) {
+        ctx.mutator().write_barrier(self.0);
+    }
+}
+
 pub trait Alloc<T> {
     fn alloc(self, ctx: Context) -> T;
 }
@@ -0,0 +1,794 @@
+//! Conversion of values of recursive data types.
+//!
+//! Values of `#[rewrite]` structs and enums are nodes of a graph on the heap, which can be
+//! deep, shared, and cyclic. Their conversions therefore never recurse into the nodes which
+//! they reference. The first conversion of a node starts a session, in which the nodes that it
+//! reaches are converted from an explicit stack:
+//!
+//! * A node which is reached many times is converted once. Its sendable form is shared through
+//!   an [`Arc`], and the sharable form of that is in turn allocated once.
+//! * A reference from a node to one of its ancestors becomes a [`Shared::Ref`] which refers
+//!   back to the ancestor by id. When received, the ancestor is allocated early from the
+//!   placeholder of its type (see [`DynSharable::hollow`]), and is filled in when the
+//!   conversion of the ancestor itself completes.
+//!
+//! The sendable form of the node which started a session also remembers the order in which
+//! the nodes were completed, so that the sharable form can be built bottom-up without
+//! recursion.
+//!
+//! Sendable nodes are serialized without recursion as well. The node which starts a session is
+//! written as the list of the contents of the nodes of the session, starting with its own. The
+//! nodes are numbered by their position in the list, and a nested node is written as
+//! `{"Ref":n}`, where `n` is its number. Nodes are listed in the order in which they are first
+//! referenced, so that the type of every node is known before it is read, also in formats which
+//! are not self-describing. The contents of the nodes are written and read through
+//! [`erased_serde`] by the serializer and deserializer of the whole value. A node which started
+//! a session of its own is written as `{"Graph":[...]}` where it is nested.
+//!
+//! A value which is read back refers to its nested nodes through [`Shared::Ref`], and remembers
+//! an order in which they can be converted, like a value which is converted in-process.
+
+use crate::prelude::*;
+
+use serde::de::DeserializeSeed;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+use serde::ser::SerializeSeq;
+
+use std::any::type_name;
+use std::any::Any;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::thread::LocalKey;
+
+/// The sharable form of a node.
+pub trait Encode: Sharable {
+    /// The sendable form of the contents of the node.
+    type Node: Send + Sync + 'static;
+    type Sendable: Decode<Node = Self::Node>;
+    /// Identifies the node by its address.
+    fn key(&self) -> usize;
+    /// Converts the fields of the node.
+    fn encode(&self, ctx: Context) -> Self::Node;
+}
+
+/// The sendable form of a node.
+pub trait Decode: Sendable + From<Shared<Self::Node>> {
+    type Node: Serialize + DeserializeOwned + Send + Sync + 'static;
+    /// The sharable form of the contents of the node.
+    type Concrete;
+    fn shared(&self) -> &Shared<Self::Node>;
+    /// Converts the fields of the node.
+    fn decode(node: &Self::Node, ctx: Context) -> Self::Concrete;
+    fn alloc(concrete: Self::Concrete, ctx: Context) -> Self::T;
+    /// Replaces the contents of a node which was allocated early.
+    fn fill(node: &Self::T, concrete: Self::Concrete, ctx: Context);
+}
+
+/// A node which has been converted into its sendable form.
+pub enum Shared<C> {
+    Node {
+        /// The id by which references refer to the node, if there are any.
+        id: std::option::Option<u32>,
+        node: Arc<C>,
+        /// The completion order of the session which was started by the node.
+        order: std::option::Option<Order>,
+    },
+    /// A reference to another node of the session by its id.
+    Ref(u32),
+}
+
+type Order = Arc<[Step]>;
+
+/// A node to convert into its sharable form, together with functions which convert and
+/// serialize it.
+pub struct Step {
+    node: Box<dyn Any + Send + Sync>,
+    /// The address of the contents of the node.
+    key: usize,
+    /// The id by which references refer to the node, if there are any.
+    id: std::option::Option<u32>,
+    run: fn(&(dyn Any + Send + Sync), Context),
+    contents: fn(&(dyn Any + Send + Sync)) -> &dyn erased_serde::Serialize,
+}
+
+impl Step {
+    fn new<S: Decode>(node: S) -> Step {
+        let (key, id) = match node.shared() {
+            Shared::Node { id, node, .. } => (address(node), *id),
+            Shared::Ref(id) => unreachable!("Found reference {} in place of a node", id),
+        };
+        Step {
+            node: Box::new(node),
+            key,
+            id,
+            run: |node, ctx| {
+                let node = node.downcast_ref::<S>().unwrap();
+                visit::<S>(node.shared(), ctx);
+            },
+            contents: contents::<S>,
+        }
+    }
+}
+
+fn contents<S: Decode>(node: &(dyn Any + Send + Sync)) -> &dyn erased_serde::Serialize {
+    match node.downcast_ref::<S>().unwrap().shared() {
+        Shared::Node { node, .. } => &**node,
+        Shared::Ref(id) => unreachable!("Found reference {} in place of a node", id),
+    }
+}
+
+fn address<C>(node: &Arc<C>) -> usize {
+    Arc::as_ptr(node) as usize
+}
+
+impl<C> From<C> for Shared<C> {
+    fn from(node: C) -> Self {
+        Shared::Node {
+            id: None,
+            node: Arc::new(node),
+            order: None,
+        }
+    }
+}
+
+impl<C> Clone for Shared<C> {
+    fn clone(&self) -> Self {
+        match self {
+            Shared::Node { id, node, order } => Shared::Node {
+                id: *id,
+                node: node.clone(),
+                order: order.clone(),
+            },
+            Shared::Ref(id) => Shared::Ref(*id),
+        }
+    }
+}
+
+impl<C: Debug> Debug for Shared<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Shared::Node { node, .. } => Debug::fmt(node, f),
+            Shared::Ref(id) => write!(f, "Ref({})", id),
+        }
+    }
+}
+
+impl<C> Shared<C> {
+    /// Returns the contents of the node, or an error if it is a reference to another node of
+    /// its session, which is only resolved when the value is converted.
+    pub fn node(&self) -> std::result::Result<&C, Unresolved> {
+        match self {
+            Shared::Node { node, .. } => Ok(node),
+            Shared::Ref(id) => Err(Unresolved(*id)),
+        }
+    }
+}
+
+/// The error of reading the contents of a [`Shared::Ref`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Unresolved(pub u32);
+
+impl fmt::Display for Unresolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Reference {} can only be resolved by converting the value which contains it",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for Unresolved {}
+
+/// The form in which a node is serialized when it is nested in another node.
+#[derive(Serialize, Deserialize)]
+enum Wire<G> {
+    /// A node of the session, by number.
+    Ref(u32),
+    /// A node which started a session of its own.
+    Graph(G),
+}
+
+/// A node which is written together with the nodes of its session.
+struct Graph<T>(T);
+
+/// Clears a session when it ends, also if a conversion panics.
+struct Session<T: 'static>(&'static LocalKey<RefCell<std::option::Option<T>>>);
+
+impl<T: Default + 'static> Session<T> {
+    fn start(key: &'static LocalKey<RefCell<std::option::Option<T>>>) -> Self {
+        key.with(|s| *s.borrow_mut() = Some(T::default()));
+        Session(key)
+    }
+
+    fn end(self) -> T {
+        self.0.with(|s| s.borrow_mut().take()).unwrap()
+    }
+}
+
+impl<T: 'static> Drop for Session<T> {
+    fn drop(&mut self) {
+        self.0.with(|s| s.borrow_mut().take());
+    }
+}
+
+thread_local! {
+    static ENCODER: RefCell<std::option::Option<Encoder>> = RefCell::new(None);
+    static DECODER: RefCell<std::option::Option<Decoder>> = RefCell::new(None);
+    static WRITER: RefCell<std::option::Option<Writer>> = RefCell::new(None);
+    static READER: RefCell<std::option::Option<Reader>> = RefCell::new(None);
+    static HOLLOW: RefCell<HashSet<TypeId>> = RefCell::new(HashSet::new());
+}
+
+/// Runs `f` on the current session of `key`.
+fn with<T: 'static, R>(
+    key: &'static LocalKey<RefCell<std::option::Option<T>>>,
+    f: impl FnOnce(&mut T) -> R,
+) -> R {
+    key.with(|s| f(s.borrow_mut().as_mut().unwrap()))
+}
+
+/// Runs `f` with `key` cleared, and restores it afterwards.
+fn without<T: 'static, R>(
+    key: &'static LocalKey<RefCell<std::option::Option<T>>>,
+    f: impl FnOnce() -> R,
+) -> R {
+    let session = key.with(|s| s.borrow_mut().take());
+    let result = f();
+    key.with(|s| *s.borrow_mut() = session);
+    result
+}
+
+/// Runs `f` outside of the current sessions, for conversions whose results are not part of
+/// the graph, such as erased values which are encoded separately.
+pub fn detached<R>(f: impl FnOnce() -> R) -> R {
+    without(&ENCODER, || {
+        without(&DECODER, || without(&WRITER, || without(&READER, f)))
+    })
+}
+
+/// Builds the placeholder of a node with `f`, or returns `None` if the placeholder of a node of
+/// the same type is already being built, since it would then have to contain itself.
+pub fn hollow<X: 'static>(f: impl FnOnce() -> std::option::Option<X>) -> std::option::Option<X> {
+    if !HOLLOW.with(|h| h.borrow_mut().insert(TypeId::of::<X>())) {
+        return None;
+    }
+    let x = f();
+    HOLLOW.with(|h| h.borrow_mut().remove(&TypeId::of::<X>()));
+    x
+}
+
+/// A node whose conversion is pending.
+trait Pending {
+    /// Converts the node, or returns `false` if it references nodes which are not converted yet.
+    fn convert(&self, ctx: Context) -> bool;
+}
+
+struct Task<X>(X);
+
+enum Encoding {
+    /// The node is being converted, and is an ancestor of the nodes which are converted until
+    /// it completes. Back references to it have the id, if there are any.
+    Open(std::option::Option<u32>),
+    Done(Box<dyn Any>),
+}
+
+#[derive(Default)]
+struct Encoder {
+    stack: std::vec::Vec<(usize, Box<dyn Pending>)>,
+    nodes: HashMap<usize, Encoding>,
+    steps: std::vec::Vec<Step>,
+    cycles: u32,
+    /// Set when the node which is being converted references a node that is not converted.
+    missing: bool,
+}
+
+impl<X: Encode> Pending for Task<X> {
+    fn convert(&self, ctx: Context) -> bool {
+        let node = self.0.encode(ctx);
+        with(&ENCODER, |e| {
+            if std::mem::take(&mut e.missing) {
+                return false;
+            }
+            let key = self.0.key();
+            let id = match e.nodes.get(&key) {
+                Some(Encoding::Open(id)) => *id,
+                _ => unreachable!("Converted node {} twice", type_name::<X>()),
+            };
+            let shared = Shared::Node {
+                id,
+                node: Arc::new(node),
+                order: None,
+            };
+            e.steps.push(Step::new(X::Sendable::from(shared.clone())));
+            e.nodes.insert(key, Encoding::Done(Box::new(shared)));
+            true
+        })
+    }
+}
+
+impl Encoder {
+    fn visit<X: Encode>(&mut self, x: &X, key: usize) -> Shared<X::Node> {
+        match self.nodes.get_mut(&key) {
+            Some(Encoding::Done(shared)) => shared
+                .downcast_ref::<Shared<X::Node>>()
+                .unwrap_or_else(|| {
+                    panic!("Found node {} at the address of another", type_name::<X>())
+                })
+                .clone(),
+            Some(Encoding::Open(id)) => Shared::Ref(*id.get_or_insert_with(|| {
+                self.cycles += 1;
+                self.cycles - 1
+            })),
+            None => {
+                self.stack.push((key, Box::new(Task(x.clone()))));
+                self.missing = true;
+                // Discarded, as the referencing node is converted again later.
+                Shared::Ref(u32::MAX)
+            }
+        }
+    }
+
+    /// Takes the next node to convert, together with the position at which to put it back if
+    /// it cannot be converted yet.
+    fn next(&mut self) -> std::option::Option<(usize, Box<dyn Pending>, usize)> {
+        while let Some((key, task)) = self.stack.pop() {
+            if !matches!(self.nodes.get(&key), Some(Encoding::Done(_))) {
+                self.nodes.entry(key).or_insert(Encoding::Open(None));
+                return Some((key, task, self.stack.len()));
+            }
+        }
+        None
+    }
+}
+
+/// Converts a node into its sendable form.
+pub fn into_sendable<X: Encode>(x: &X, ctx: Context) -> Shared<X::Node> {
+    let key = x.key();
+    if let Some(shared) = ENCODER.with(|e| e.borrow_mut().as_mut().map(|e| e.visit(x, key))) {
+        return shared;
+    }
+    let session = Session::start(&ENCODER);
+    with(&ENCODER, |e| e.stack.push((key, Box::new(Task(x.clone())))));
+    while let Some((key, task, at)) = with(&ENCODER, |e| e.next()) {
+        if !task.convert(ctx) {
+            with(&ENCODER, |e| e.stack.insert(at, (key, task)));
+        }
+    }
+    let mut encoder = session.end();
+    // Steps are kept in reverse so that dropping them drops parents before their children,
+    // which are then not dropped recursively.
+    encoder.steps.reverse();
+    match encoder.nodes.remove(&key) {
+        Some(Encoding::Done(shared)) => match *shared.downcast::<Shared<X::Node>>().unwrap() {
+            Shared::Node { id, node, .. } => Shared::Node {
+                id,
+                node,
+                order: Some(encoder.steps.into()),
+            },
+            Shared::Ref(_) => unreachable!(),
+        },
+        _ => unreachable!("Node {} was not converted", type_name::<X>()),
+    }
+}
+
+#[derive(Default)]
+struct Decoder {
+    nodes: HashMap<usize, Box<dyn Any>>,
+    /// Nodes which are converted, by id.
+    ids: HashMap<u32, Box<dyn Any>>,
+    /// Nodes which were allocated early since they are referenced before they are converted.
+    early: HashMap<u32, Box<dyn Any>>,
+}
+
+/// Converts a node into its sharable form.
+pub fn into_sharable<S: Decode>(s: &S, ctx: Context) -> S::T {
+    let shared = s.shared();
+    let order = match shared {
+        Shared::Node { order, .. } => order.as_ref(),
+        Shared::Ref(_) => None,
+    };
+    if order.is_none() && DECODER.with(|d| d.borrow().is_some()) {
+        return visit::<S>(shared, ctx);
+    }
+    // A node which started a session is converted in a session of its own, also if it is
+    // nested in another one.
+    without(&DECODER, || {
+        let _session = Session::<Decoder>::start(&DECODER);
+        for step in order.iter().flat_map(|order| order.iter().rev()) {
+            (step.run)(&*step.node, ctx);
+        }
+        visit::<S>(shared, ctx)
+    })
+}
+
+fn visit<S: Decode>(shared: &Shared<S::Node>, ctx: Context) -> S::T {
+    match shared {
+        Shared::Ref(id) => {
+            let found = with(&DECODER, |d| {
+                d.ids
+                    .get(id)
+                    .or_else(|| d.early.get(id))
+                    .map(|x| downcast::<S::T>(&**x))
+            });
+            found.unwrap_or_else(|| {
+                let x = <S::T as DynSharable>::hollow(ctx).unwrap_or_else(|| {
+                    panic!(
+                        "Cannot rebuild a cycle through {}, which has no placeholder",
+                        type_name::<S::T>()
+                    )
+                });
+                with(&DECODER, |d| d.early.insert(*id, Box::new(x.clone())));
+                x
+            })
+        }
+        Shared::Node { id, node, .. } => {
+            let key = address(node);
+            let done = with(&DECODER, |d| {
+                d.nodes.get(&key).map(|x| downcast::<S::T>(&**x))
+            });
+            if let Some(x) = done {
+                return x;
+            }
+            let concrete = S::decode(node, ctx);
+            let early = id.and_then(|id| with(&DECODER, |d| d.early.remove(&id)));
+            let x = match early {
+                Some(x) => {
+                    let x = downcast::<S::T>(&*x);
+                    S::fill(&x, concrete, ctx);
+                    x
+                }
+                None => S::alloc(concrete, ctx),
+            };
+            with(&DECODER, |d| {
+                d.nodes.insert(key, Box::new(x.clone()));
+                if let Some(id) = id {
+                    d.ids.insert(*id, Box::new(x.clone()));
+                }
+            });
+            x
+        }
+    }
+}
+
+fn downcast<T: Clone + 'static>(x: &dyn Any) -> T {
+    x.downcast_ref::<T>()
+        .unwrap_or_else(|| panic!("Found node {} at the address of another", type_name::<T>()))
+        .clone()
+}
+
+#[derive(Default)]
+struct Writer {
+    /// The nodes of the session.
+    order: std::option::Option<Order>,
+    /// Positions of the nodes in the order, by address.
+    addresses: HashMap<usize, usize>,
+    /// Positions of the nodes in the order, by the id which references refer to them by.
+    ids: HashMap<u32, usize>,
+    /// Numbers of the nodes which are reached, by position.
+    numbers: HashMap<usize, u32>,
+    /// Positions of the nodes which are numbered but not yet written.
+    pending: VecDeque<usize>,
+    next: u32,
+}
+
+impl Writer {
+    /// Starts the session of the node at address `key`, and returns the number of nodes which
+    /// are written.
+    fn start(&mut self, key: usize, order: &std::option::Option<Order>) -> usize {
+        let len = order.as_ref().map_or(0, |order| order.len());
+        for (i, step) in order.iter().flat_map(|order| order.iter()).enumerate() {
+            self.addresses.insert(step.key, i);
+            if let Some(id) = step.id {
+                self.ids.insert(id, i);
+            }
+        }
+        self.order = order.clone();
+        self.next = 1;
+        match self.addresses.get(&key) {
+            Some(i) => {
+                self.numbers.insert(*i, 0);
+                len
+            }
+            None => len + 1,
+        }
+    }
+
+    /// Returns the number of a nested node, or `None` if it is not a node of the session. A
+    /// node which is reached for the first time is scheduled to be written.
+    fn reach<C>(
+        &mut self,
+        shared: &Shared<C>,
+    ) -> std::result::Result<std::option::Option<u32>, std::string::String> {
+        let i = match shared {
+            Shared::Node { order: Some(_), .. } => None,
+            Shared::Node { node, .. } => self.addresses.get(&address(node)).copied(),
+            Shared::Ref(id) => Some(
+                self.ids
+                    .get(id)
+                    .copied()
+                    .ok_or_else(|| format!("Found a reference to unknown node {}", id))?,
+            ),
+        };
+        Ok(i.map(|i| {
+            let (next, pending) = (&mut self.next, &mut self.pending);
+            *self.numbers.entry(i).or_insert_with(|| {
+                pending.push_back(i);
+                *next += 1;
+                *next - 1
+            })
+        }))
+    }
+}
+
+/// Writes a node together with the nodes of its session.
+fn write_session<C, S>(shared: &Shared<C>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    C: Serialize + Send + Sync + 'static,
+    S: serde::Serializer,
+{
+    use serde::ser::Error;
+    let (node, order) = match shared {
+        Shared::Node { node, order, .. } => (node, order),
+        Shared::Ref(id) => {
+            return Err(S::Error::custom(format!(
+                "Cannot serialize reference {} outside of its session",
+                id
+            )))
+        }
+    };
+    without(&WRITER, || {
+        let _session = Session::<Writer>::start(&WRITER);
+        let len = with(&WRITER, |w| w.start(address(node), order));
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        seq.serialize_element(&**node)?;
+        let mut written = 1;
+        while let Some(i) = with(&WRITER, |w| w.pending.pop_front()) {
+            let step = &order.as_ref().unwrap()[i];
+            seq.serialize_element((step.contents)(&*step.node))?;
+            written += 1;
+        }
+        if written != len {
+            return Err(S::Error::custom(format!(
+                "Found {} nodes which are not reachable",
+                len - written
+            )));
+        }
+        seq.end()
+    })
+}
+
+impl<C: Serialize + Send + Sync + 'static> Serialize for Graph<&Shared<C>> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        write_session(self.0, serializer)
+    }
+}
+
+impl<C: Serialize + Send + Sync + 'static> Serialize for Shared<C> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+        if WRITER.with(|w| w.borrow().is_none()) {
+            return write_session(self, serializer);
+        }
+        match with(&WRITER, |w| w.reach(self)).map_err(S::Error::custom)? {
+            Some(n) => Wire::<Graph<&Self>>::Ref(n).serialize(serializer),
+            None => Wire::Graph(Graph(self)).serialize(serializer),
+        }
+    }
+}
+
+type Read = for<'i> fn(
+    &mut dyn erased_serde::Deserializer<'i>,
+    u32,
+) -> std::result::Result<Step, erased_serde::Error>;
+
+/// A node which is reached while reading.
+#[derive(Default)]
+struct Slot {
+    /// The type of the node and how to read it, which are known once it is referenced.
+    read: std::option::Option<(TypeId, Read)>,
+    step: std::option::Option<Step>,
+    /// The numbers of the nodes which the node refers to.
+    edges: std::vec::Vec<u32>,
+}
+
+#[derive(Default)]
+struct Reader {
+    /// The nodes which are referenced, by number.
+    slots: HashMap<u32, Slot>,
+    /// The number of the node which is being read.
+    current: u32,
+}
+
+impl Reader {
+    /// Records a reference from the node which is being read to node `n`.
+    fn reach<S: Decode>(&mut self, n: u32) -> std::result::Result<(), std::string::String> {
+        let slot = self.slots.entry(n).or_default();
+        match slot.read {
+            Some((ty, _)) if ty != TypeId::of::<S>() => {
+                return Err(format!(
+                    "Found a reference to node {} as {}, which is of another type",
+                    n,
+                    type_name::<S>()
+                ))
+            }
+            Some(_) => {}
+            None => slot.read = Some((TypeId::of::<S>(), read::<S>)),
+        }
+        let current = self.current;
+        self.slots.get_mut(&current).unwrap().edges.push(n);
+        Ok(())
+    }
+
+    /// Returns the nodes which are reached from the node which started the session, ordered
+    /// such that every node comes after the nodes that it refers to, except for its ancestors.
+    fn finish(mut self) -> std::result::Result<Order, std::string::String> {
+        let unread = self.slots.iter().filter(|(_, slot)| slot.step.is_none());
+        if let Some(n) = unread.map(|(n, _)| *n).min() {
+            return Err(format!("Found a reference to unknown node {}", n));
+        }
+        let mut steps = std::vec::Vec::new();
+        let mut visited = HashSet::from([0]);
+        let mut stack = vec![(0, 0)];
+        while let Some((n, i)) = stack.last_mut() {
+            let slot = self.slots.get_mut(n).unwrap();
+            match slot.edges.get(*i) {
+                Some(&m) => {
+                    *i += 1;
+                    if visited.insert(m) {
+                        stack.push((m, 0));
+                    }
+                }
+                None => {
+                    steps.extend(slot.step.take());
+                    stack.pop();
+                }
+            }
+        }
+        // In reverse, as for the sendable form of a converted node.
+        steps.reverse();
+        Ok(steps.into())
+    }
+}
+
+fn read<S: Decode>(
+    deserializer: &mut dyn erased_serde::Deserializer,
+    n: u32,
+) -> std::result::Result<Step, erased_serde::Error> {
+    let node = erased_serde::deserialize::<S::Node>(deserializer)?;
+    Ok(Step::new(S::from(Shared::Node {
+        id: Some(n),
+        node: Arc::new(node),
+        order: None,
+    })))
+}
+
+/// Reads the contents of the nodes of a session, the first of which is the node which started
+/// it.
+struct Nodes<C>(PhantomData<C>);
+
+impl<'i, C: DeserializeOwned> Visitor<'i> for Nodes<C> {
+    type Value = C;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a list of nodes")
+    }
+
+    fn visit_seq<A: SeqAccess<'i>>(self, mut seq: A) -> std::result::Result<C, A::Error> {
+        use serde::de::Error;
+        let node = seq
+            .next_element::<C>()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let mut n = 1;
+        while let Some(step) = seq.next_element_seed(Element(n))? {
+            with(&READER, |r| r.slots.get_mut(&n).unwrap().step = Some(step));
+            n += 1;
+        }
+        Ok(node)
+    }
+}
+
+/// Reads node `n`, whose type is known from the references to it.
+struct Element(u32);
+
+impl<'i> DeserializeSeed<'i> for Element {
+    type Value = Step;
+
+    fn deserialize<D: serde::Deserializer<'i>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Step, D::Error> {
+        use serde::de::Error;
+        let n = self.0;
+        let read = with(&READER, |r| {
+            r.current = n;
+            r.slots.get(&n).and_then(|slot| slot.read)
+        });
+        let (_, read) = read.ok_or_else(|| {
+            D::Error::custom(format!("Found node {} before any reference to it", n))
+        })?;
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+        read(&mut deserializer, n).map_err(D::Error::custom)
+    }
+}
+
+/// Reads a node together with the nodes of its session.
+fn read_session<'i, C, D>(deserializer: D) -> std::result::Result<Shared<C>, D::Error>
+where
+    C: DeserializeOwned + Concrete + Send + Sync + 'static,
+    C::Abstract: Decode<Node = C>,
+    D: serde::Deserializer<'i>,
+{
+    use serde::de::Error;
+    without(&READER, || {
+        let session = Session::<Reader>::start(&READER);
+        with(&READER, |r| {
+            let read: Read = read::<C::Abstract>;
+            let slot = Slot {
+                read: Some((TypeId::of::<C::Abstract>(), read)),
+                ..Default::default()
+            };
+            r.slots.insert(0, slot)
+        });
+        let node = Arc::new(deserializer.deserialize_seq(Nodes::<C>(PhantomData))?);
+        let mut reader = session.end();
+        let root = Shared::Node {
+            id: Some(0),
+            node: node.clone(),
+            order: None,
+        };
+        reader.slots.get_mut(&0).unwrap().step = Some(Step::new(C::Abstract::from(root)));
+        Ok(Shared::Node {
+            id: Some(0),
+            node,
+            order: Some(reader.finish().map_err(D::Error::custom)?),
+        })
+    })
+}
+
+impl<'i, C> Deserialize<'i> for Graph<Shared<C>>
+where
+    C: DeserializeOwned + Concrete + Send + Sync + 'static,
+    C::Abstract: Decode<Node = C>,
+{
+    fn deserialize<D: serde::Deserializer<'i>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        read_session(deserializer).map(Graph)
+    }
+}
+
+impl<'i, C> Deserialize<'i> for Shared<C>
+where
+    C: DeserializeOwned + Concrete + Send + Sync + 'static,
+    C::Abstract: Decode<Node = C>,
+{
+    fn deserialize<D: serde::Deserializer<'i>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error;
+        if READER.with(|r| r.borrow().is_none()) {
+            return read_session(deserializer);
+        }
+        match Wire::<Graph<Self>>::deserialize(deserializer)? {
+            Wire::Ref(n) => {
+                with(&READER, |r| r.reach::<C::Abstract>(n)).map_err(D::Error::custom)?;
+                Ok(Shared::Ref(n))
+            }
+            Wire::Graph(Graph(shared)) => Ok(shared),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::prelude::*;
+
+pub mod sharable {
+    use crate::prelude::*;
+
+    use indexmap::map::MutableKeys;
+    use indexmap::IndexMap;
+
+    #[derive(
+        Clone, From, Deref, DerefMut, Finalize, Collectable, Debug, Send, Sync, Unpin, Trace,
+    )]
+    #[from(forward)]
+    pub struct Map<K: Sharable + Hash + Eq, V: Sharable>(pub Gc<ConcreteMap<K, V>>);
+
+    /// The table is owned by the heap object and released by its finalizer. Iteration order is
+    /// deterministic, which keeps programs reproducible.
+    #[derive(Deref, DerefMut, Finalize, Collectable, Debug)]
+    pub struct ConcreteMap<K, V>(pub IndexMap<K, V>);
+
+    unsafe impl<K: Trace + Hash + Eq, V: Trace> Trace for ConcreteMap<K, V> {
+        fn trace(&mut self, vis: &mut dyn Visitor) {
+            self.0.retain2(|k, v| {
+                k.trace(vis);
+                v.trace(vis);
+                true
+            });
+        }
+    }
+
+    impl<K: Sharable + Hash + Eq, V: Sharable> Alloc<Map<K, V>> for ConcreteMap<K, V> {
+        fn alloc(self, ctx: Context) -> Map<K, V> {
+            Map(ctx.mutator().allocate(self, AllocationSpace::New).into())
+        }
+    }
+}
+
+mod sendable {
+    use crate::prelude::*;
+
+    /// Entries are sent in insertion order, so the order survives conversions.
+    #[derive(Clone, From, Send, Serialize, Deserialize)]
+    #[serde(bound(serialize = "K: Hash + Eq", deserialize = "K: Hash + Eq"))]
+    #[from(forward)]
+    pub struct Map<K: Sendable, V: Sendable>(pub ConcreteMap<K, V>);
+
+    pub type ConcreteMap<K, V> = indexmap::IndexMap<K, V>;
+}
+
+impl<K, V> DynSharable for sharable::Map<K, V>
+where
+    K: Sharable + Hash + Eq,
+    K::T: Sendable<T = K> + Hash + Eq,
+    V: Sharable,
+{
+    type T = sendable::Map<K::T, V::T>;
+    fn into_sendable(&self, ctx: Context) -> Self::T {
+        self.0
+            .iter()
+            .map(|(k, v)| (k.into_sendable(ctx), v.into_sendable(ctx)))
+            .collect::<sendable::ConcreteMap<_, _>>()
+            .into()
+    }
+    fn hollow(ctx: Context) -> std::option::Option<Self> {
+        Some(Map::new(ctx))
+    }
+}
+
+impl<K, V> DynSendable for sendable::Map<K, V>
+where
+    K: Sendable + Hash + Eq,
+    K::T: DynSharable<T = K> + Hash + Eq,
+    V: Sendable,
+{
+    type T = sharable::Map<K::T, V::T>;
+    fn into_sharable(&self, ctx: Context) -> Self::T {
+        let mut s = Map::<K::T, V::T>::new(ctx);
+        for (k, v) in self.0.iter() {
+            s.0.insert(k.into_sharable(ctx), v.into_sharable(ctx));
+        }
+        s
+    }
+}
+
+pub use sharable::Map;
+
+#[rewrite]
+impl<K: Sharable + Hash + Eq, V: Sharable> Map<K, V> {
+    pub fn new(ctx: Context) -> Map<K, V> {
+        sharable::ConcreteMap(indexmap::IndexMap::new()).alloc(ctx)
+    }
+
+    pub fn insert(mut self, key: K, value: V, ctx: Context) -> Option<V> {
+        self.0.insert(key, value).alloc(ctx)
+    }
+
+    pub fn get(self, key: K, ctx: Context) -> Option<V> {
+        self.0.get(&key).cloned().alloc(ctx)
+    }
+
+    pub fn remove(mut self, key: K, ctx: Context) -> Option<V> {
+        self.0.shift_remove(&key).alloc(ctx)
+    }
+
+    pub fn contains(self, key: K, ctx: Context) -> bool {
+        self.0.contains_key(&key)
+    }
+
+    pub fn len(self, ctx: Context) -> usize {
+        self.0.len()
+    }
+
+    /// Returns a snapshot of the entries in insertion order, so the map may be updated while
+    /// iterating over them.
+    pub fn iter(self, ctx: Context) -> Vec<(K, V)> {
+        Vec::from_iter(self.0.iter().map(|(k, v)| (k.clone(), v.clone())), ctx)
+    }
+}
@@ -1,13 +1,29 @@
 pub mod functions;
 pub mod garbage;
+pub mod graphs;
 pub mod primitives;
 pub mod strings;
+pub mod times;
+pub mod tuples;
 pub mod vectors;
+pub mod maps;
+pub mod bigints;
+pub mod bytes;
+pub mod decimals;
+pub mod options;
+pub mod records;
+pub mod results;
+pub mod sets;
 #[cfg(feature = "dataframes")]
 pub mod dataframe;
+#[cfg(feature = "dataframes")]
 pub mod series;
 pub mod channels;
 pub mod cells;
+pub mod erased;
+pub mod format;
+#[cfg(feature = "arrow")]
+pub mod arrow;
 
 use crate::data::garbage::Garbage;
 
@@ -35,17 +51,25 @@ pub trait DynSendable: AsyncSafe + DynClone {
 pub trait DynSharable: AsyncSafe + DynClone + Garbage + Debug {
     type T: Sendable;
     fn into_sendable(&self, ctx: Context) -> Self::T;
+    /// Returns a placeholder which a node of a cyclic value can be allocated with before its
+    /// contents are converted, if there is one.
+    fn hollow(_: Context) -> std::option::Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 pub trait AsyncSafe: Send + Sync + Unpin {}
 impl<T> AsyncSafe for T where T: Send + Sync + Unpin {}
 
-pub trait Sendable: Sized + DynSendable + Clone + Serialize + DeserializeOwned {}
-pub trait Sharable: Sized + DynSharable + Clone {}
+pub trait Sendable: Sized + DynSendable + Clone + Serialize + DeserializeOwned + 'static {}
+pub trait Sharable: Sized + DynSharable + Clone + 'static {}
 pub trait DataItem: Sized + Copy + Debug + AsyncSafe {}
 
-impl<T> Sharable for T where T: Sized + DynSharable + Clone {}
-impl<T> Sendable for T where T: Sized + DynSendable + Clone + Serialize + DeserializeOwned {}
+impl<T> Sharable for T where T: Sized + DynSharable + Clone + 'static {}
+impl<T> Sendable for T where T: Sized + DynSendable + Clone + Serialize + DeserializeOwned + 'static {}
 impl<T> DataItem for T where T: Sized + Copy + Debug + AsyncSafe {}
 
 dyn_clone::clone_trait_object!(<T> DynSharable<T = T>);
@@ -62,7 +86,18 @@ macro_rules! convert_reflexive {
             type T = Self;
             fn into_sharable(&self, _: Context) -> Self { self.clone() }
         }
-    }
+    };
+    {$ty:ty, hollow: $hollow:expr} => {
+        impl DynSharable for $ty {
+            type T = Self;
+            fn into_sendable(&self, _: Context) -> Self { self.clone() }
+            fn hollow(_: Context) -> std::option::Option<Self> { Some($hollow) }
+        }
+        impl DynSendable for $ty {
+            type T = Self;
+            fn into_sharable(&self, _: Context) -> Self { self.clone() }
+        }
+    };
 }
 
 pub use convert_reflexive;
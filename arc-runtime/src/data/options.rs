@@ -0,0 +1,67 @@
+//! Optional values, which arc code can inspect through `is!(OptionSome, x)` and
+//! `unwrap!(OptionSome, x)`.
+
+use crate::prelude::*;
+
+#[rewrite]
+pub enum Option<T> {
+    OptionSome(T),
+    OptionNone(unit),
+}
+
+impl<T: Sharable> Alloc<Option<T>> for std::option::Option<T> {
+    fn alloc(self, ctx: Context) -> Option<T> {
+        match self {
+            Some(v) => OptionSome(v).alloc(ctx),
+            None => OptionNone(unit).alloc(ctx),
+        }
+    }
+}
+
+#[rewrite]
+impl<T: Sharable> Option<T> {
+    pub fn some(v: T, ctx: Context) -> Option<T> {
+        OptionSome(v).alloc(ctx)
+    }
+
+    pub fn none(ctx: Context) -> Option<T> {
+        OptionNone(unit).alloc(ctx)
+    }
+
+    pub fn is_some(self, ctx: Context) -> bool {
+        matches!(&*self.0, OptionSome(_))
+    }
+
+    pub fn is_none(self, ctx: Context) -> bool {
+        matches!(&*self.0, OptionNone(_))
+    }
+
+    pub fn map<U: Sharable, F: Call<(T,), U>>(self, f: F, ctx: Context) -> Option<U> {
+        match &*self.0 {
+            OptionSome(v) => OptionSome(f.call((v.clone(),), ctx)).alloc(ctx),
+            OptionNone(_) => OptionNone(unit).alloc(ctx),
+        }
+    }
+
+    pub fn and_then<U: Sharable, F: Call<(T,), Option<U>>>(self, f: F, ctx: Context) -> Option<U> {
+        match &*self.0 {
+            OptionSome(v) => f.call((v.clone(),), ctx),
+            OptionNone(_) => OptionNone(unit).alloc(ctx),
+        }
+    }
+
+    /// Returns the value of an `OptionSome`, and panics on an `OptionNone`.
+    pub fn unwrap(self, ctx: Context) -> T {
+        match &*self.0 {
+            OptionSome(v) => v.clone(),
+            OptionNone(_) => panic!("Called unwrap on an OptionNone"),
+        }
+    }
+
+    pub fn unwrap_or(self, default: T, ctx: Context) -> T {
+        match &*self.0 {
+            OptionSome(v) => v.clone(),
+            OptionNone(_) => default,
+        }
+    }
+}
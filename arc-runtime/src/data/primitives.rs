@@ -21,26 +21,28 @@ pub use u16;
 pub use u32;
 pub use u64;
 pub use u8;
+pub use usize;
 #[allow(non_camel_case_types)]
 pub type unit = ();
 pub type Unit = ();
 pub use std::ops::Range;
 
-convert_reflexive!(i8);
-convert_reflexive!(i16);
-convert_reflexive!(i32);
-convert_reflexive!(i64);
-convert_reflexive!(i128);
-convert_reflexive!(u8);
-convert_reflexive!(u16);
-convert_reflexive!(u32);
-convert_reflexive!(u64);
-convert_reflexive!(u128);
-convert_reflexive!(f32);
-convert_reflexive!(f64);
-convert_reflexive!(bool);
-// convert_reflexive!(char);
-convert_reflexive!(unit);
+convert_reflexive!(i8, hollow: 0);
+convert_reflexive!(i16, hollow: 0);
+convert_reflexive!(i32, hollow: 0);
+convert_reflexive!(i64, hollow: 0);
+convert_reflexive!(i128, hollow: 0);
+convert_reflexive!(u8, hollow: 0);
+convert_reflexive!(u16, hollow: 0);
+convert_reflexive!(u32, hollow: 0);
+convert_reflexive!(u64, hollow: 0);
+convert_reflexive!(u128, hollow: 0);
+convert_reflexive!(usize, hollow: 0);
+convert_reflexive!(f32, hollow: 0.0);
+convert_reflexive!(f64, hollow: 0.0);
+convert_reflexive!(bool, hollow: false);
+convert_reflexive!(char, hollow: '\0');
+convert_reflexive!(unit, hollow: ());
 
 alloc_identity!(i8);
 alloc_identity!(i16);
@@ -51,6 +53,8 @@ alloc_identity!(u8);
 alloc_identity!(u16);
 alloc_identity!(u32);
 alloc_identity!(u64);
+alloc_identity!(u128);
+alloc_identity!(usize);
 alloc_identity!(f32);
 alloc_identity!(f64);
 alloc_identity!(bool);
@@ -76,3 +80,50 @@ pub fn panic(s: String) {
 pub fn print(s: String) {
     println!("{}", s.as_str())
 }
+
+/// Returns the char with code point `x`, or `OptionNone` if `x` is not a valid code point.
+#[rewrite]
+pub fn char_from_u32(x: u32) -> Option<char> {
+    let c: std::option::Option<char> = x.try_into().ok();
+    c.alloc(ctx)
+}
+
+#[rewrite]
+pub fn char_to_u32(c: char) -> u32 {
+    c.into()
+}
+
+#[rewrite]
+pub fn char_to_string(c: char) -> String {
+    String::from_str(c.encode_utf8(&mut [0; 4]))
+}
+
+#[rewrite]
+pub fn char_is_alphabetic(c: char) -> bool {
+    c.is_alphabetic()
+}
+
+#[rewrite]
+pub fn char_is_numeric(c: char) -> bool {
+    c.is_numeric()
+}
+
+#[rewrite]
+pub fn char_is_alphanumeric(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+#[rewrite]
+pub fn char_is_whitespace(c: char) -> bool {
+    c.is_whitespace()
+}
+
+#[rewrite]
+pub fn char_to_ascii_uppercase(c: char) -> char {
+    c.to_ascii_uppercase()
+}
+
+#[rewrite]
+pub fn char_to_ascii_lowercase(c: char) -> char {
+    c.to_ascii_lowercase()
+}
@@ -0,0 +1,31 @@
+//! Structural records, e.g., `#{k: i32, v: i32}` in arc.
+//!
+//! A record is represented by a canonical struct per sorted set of field names, which is
+//! generated by `declare_records!` and named by `record!`:
+//!
+//! ```ignore
+//! declare_records!({k, v}, {k, ..});
+//! let r: record!(k: i32, v: i32) = record!(v = 2, k = 1);
+//! ```
+//!
+//! An open record `{k, ..}` has the known fields and an [`Erased`] record of the rest, which it
+//! gets when a closed record with a superset of its fields is [`Open`]ed. The rest is put back
+//! when the open record is [`Close`]d.
+//!
+//! `declare_records!` declares its structs in a module named `records`, so it can be invoked at
+//! most once per module, and must be given every record which is used in that module.
+
+use crate::data::erased::UneraseError;
+use crate::prelude::*;
+
+/// Conversion of a closed record into an open record of some of its fields.
+pub trait Open<T> {
+    fn open(self, ctx: Context) -> T;
+}
+
+/// Conversion of an open record back into the closed record it was opened from. Fails if the
+/// rest of the open record does not have the remaining fields of the closed record, because
+/// the open record was opened from another closed record.
+pub trait Close<T> {
+    fn close(self, ctx: Context) -> std::result::Result<T, UneraseError>;
+}
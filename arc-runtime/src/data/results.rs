@@ -0,0 +1,63 @@
+//! Results of fallible operations, which arc code can inspect through `is!(ResultOk, x)` and
+//! `unwrap!(ResultOk, x)`.
+
+use crate::prelude::*;
+
+#[rewrite]
+pub enum Result<T, E> {
+    ResultOk(T),
+    ResultErr(E),
+}
+
+impl<T: Sharable, E: Sharable> Alloc<Result<T, E>> for std::result::Result<T, E> {
+    fn alloc(self, ctx: Context) -> Result<T, E> {
+        match self {
+            Ok(v) => ResultOk(v).alloc(ctx),
+            Err(e) => ResultErr(e).alloc(ctx),
+        }
+    }
+}
+
+#[rewrite]
+impl<T: Sharable, E: Sharable> Result<T, E> {
+    pub fn ok(v: T, ctx: Context) -> Result<T, E> {
+        ResultOk(v).alloc(ctx)
+    }
+
+    pub fn err(e: E, ctx: Context) -> Result<T, E> {
+        ResultErr(e).alloc(ctx)
+    }
+
+    pub fn is_ok(self, ctx: Context) -> bool {
+        matches!(&*self.0, ResultOk(_))
+    }
+
+    pub fn is_err(self, ctx: Context) -> bool {
+        matches!(&*self.0, ResultErr(_))
+    }
+
+    pub fn map<U: Sharable, F: Call<(T,), U>>(self, f: F, ctx: Context) -> Result<U, E> {
+        match &*self.0 {
+            ResultOk(v) => ResultOk(f.call((v.clone(),), ctx)).alloc(ctx),
+            ResultErr(e) => ResultErr(e.clone()).alloc(ctx),
+        }
+    }
+
+    pub fn and_then<U: Sharable, F: Call<(T,), Result<U, E>>>(
+        self,
+        f: F,
+        ctx: Context,
+    ) -> Result<U, E> {
+        match &*self.0 {
+            ResultOk(v) => f.call((v.clone(),), ctx),
+            ResultErr(e) => ResultErr(e.clone()).alloc(ctx),
+        }
+    }
+
+    pub fn unwrap_or(self, default: T, ctx: Context) -> T {
+        match &*self.0 {
+            ResultOk(v) => v.clone(),
+            ResultErr(_) => default,
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Columns of values, backed by polars. A series is untyped, and functions which produce or
+//! consume elements are generic over the element type, which must match the type of the series.
+
+use crate::data::dataframe::from_ipc;
+use crate::data::dataframe::to_ipc;
+use crate::prelude::*;
+
+use polars::prelude::ChunkCompare;
+use polars::prelude::DataType;
+use polars::prelude::IntoSeries;
+use polars::prelude::NamedFrom;
+
+pub mod sharable {
+    use crate::prelude::*;
+
+    #[derive(
+        Clone, From, Deref, DerefMut, Debug, Collectable, Finalize, Send, Sync, Unpin, NoTrace,
+    )]
+    #[from(forward)]
+    pub struct Series(pub Gc<ConcreteSeries>);
+
+    #[derive(Debug, Collectable, NoTrace, Finalize, Send, Sync, Unpin)]
+    pub struct ConcreteSeries(pub polars::series::Series);
+
+    impl Alloc<Series> for ConcreteSeries {
+        fn alloc(self, ctx: Context) -> Series {
+            Series(ctx.mutator().allocate(self, AllocationSpace::New).into())
+        }
+    }
+}
+
+pub mod sendable {
+    use crate::prelude::*;
+
+    #[derive(Clone, From, Send, Serialize, Deserialize)]
+    #[from(forward)]
+    pub struct Series(pub ConcreteSeries);
+
+    /// A series encoded in the Arrow IPC format, as a data frame with a single column.
+    pub type ConcreteSeries = Box<[u8]>;
+}
+
+impl DynSharable for sharable::Series {
+    type T = sendable::Series;
+    fn into_sendable(&self, ctx: Context) -> Self::T {
+        let df = polars::frame::DataFrame::new(vec![self.series().clone()])
+            .unwrap_or_else(|e| panic!("Failed to encode series: {}", e));
+        to_ipc(&df).into()
+    }
+}
+
+impl DynSendable for sendable::Series {
+    type T = sharable::Series;
+    fn into_sharable(&self, ctx: Context) -> Self::T {
+        let series = from_ipc(&self.0).get_columns()[0].clone();
+        sharable::ConcreteSeries(series).alloc(ctx)
+    }
+}
+
+pub use sharable::Series;
+
+impl Interpolate for Series {}
+
+/// A type of value which can be stored in a series.
+pub trait Element: Sharable + Copy {
+    fn to_series(name: &str, values: &[Self]) -> polars::series::Series;
+    fn from_series(series: &polars::series::Series) -> std::vec::Vec<Self>;
+}
+
+macro_rules! impl_element {
+    ($($ty:ty => ($dtype:ident, $get:ident)),*) => {
+        $(
+            impl Element for $ty {
+                fn to_series(name: &str, values: &[Self]) -> polars::series::Series {
+                    polars::series::Series::new(name, values)
+                }
+
+                fn from_series(series: &polars::series::Series) -> std::vec::Vec<Self> {
+                    let series = series
+                        .cast(&DataType::$dtype)
+                        .unwrap_or_else(|e| panic!("Failed to cast series to {}: {}", stringify!($ty), e));
+                    series
+                        .$get()
+                        .unwrap()
+                        .into_iter()
+                        .map(|x| x.expect("Found null in series"))
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_element!(
+    i8 => (Int8, i8),
+    i16 => (Int16, i16),
+    i32 => (Int32, i32),
+    i64 => (Int64, i64),
+    u8 => (UInt8, u8),
+    u16 => (UInt16, u16),
+    u32 => (UInt32, u32),
+    u64 => (UInt64, u64),
+    f32 => (Float32, f32),
+    f64 => (Float64, f64),
+    bool => (Boolean, bool)
+);
+
+impl Series {
+    pub(crate) fn wrap(series: polars::series::Series, ctx: Context) -> Series {
+        sharable::ConcreteSeries(series).alloc(ctx)
+    }
+
+    pub(crate) fn series(&self) -> &polars::series::Series {
+        &self.0 .0
+    }
+
+    /// Returns the only element of a series which is the result of an aggregation.
+    fn scalar<T: Element>(series: polars::series::Series) -> T {
+        T::from_series(&series)
+            .pop()
+            .expect("Cannot aggregate an empty series")
+    }
+}
+
+#[rewrite]
+impl Series {
+    pub fn from_vec<T: Element>(name: String, values: Vec<T>, ctx: Context) -> Series {
+        let name: &str = &name.0;
+        Series::wrap(T::to_series(name, values.as_slice(ctx)), ctx)
+    }
+
+    pub fn to_vec<T: Element>(self, ctx: Context) -> Vec<T> {
+        Vec::from_iter(T::from_series(self.series()), ctx)
+    }
+
+    pub fn name(self, ctx: Context) -> String {
+        String::from_str(self.series().name(), ctx)
+    }
+
+    pub fn len(self, ctx: Context) -> usize {
+        self.series().len()
+    }
+
+    /// Returns the value at `index`, or `OptionNone` if it is out of bounds.
+    pub fn get<T: Element>(self, index: usize, ctx: Context) -> Option<T> {
+        (index < self.series().len())
+            .then(|| Series::scalar(self.series().slice(index as i64, 1)))
+            .alloc(ctx)
+    }
+
+    pub fn add(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(self.series() + other.series(), ctx)
+    }
+
+    pub fn sub(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(self.series() - other.series(), ctx)
+    }
+
+    pub fn mul(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(self.series() * other.series(), ctx)
+    }
+
+    pub fn div(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(self.series() / other.series(), ctx)
+    }
+
+    pub fn eq(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(
+            ChunkCompare::<&_>::eq(self.series(), other.series()).into_series(),
+            ctx,
+        )
+    }
+
+    pub fn neq(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(
+            ChunkCompare::<&_>::neq(self.series(), other.series()).into_series(),
+            ctx,
+        )
+    }
+
+    pub fn lt(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(self.series().lt(other.series()).into_series(), ctx)
+    }
+
+    pub fn lt_eq(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(self.series().lt_eq(other.series()).into_series(), ctx)
+    }
+
+    pub fn gt(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(self.series().gt(other.series()).into_series(), ctx)
+    }
+
+    pub fn gt_eq(self, other: Series, ctx: Context) -> Series {
+        Series::wrap(self.series().gt_eq(other.series()).into_series(), ctx)
+    }
+
+    pub fn sum<T: Element>(self, ctx: Context) -> T {
+        Series::scalar(self.series().sum_as_series())
+    }
+
+    /// The mean of the elements, or NaN if the series is empty.
+    pub fn mean(self, ctx: Context) -> f64 {
+        self.series().mean().unwrap_or(f64::NAN)
+    }
+
+    pub fn min<T: Element>(self, ctx: Context) -> T {
+        Series::scalar(self.series().min_as_series())
+    }
+
+    pub fn max<T: Element>(self, ctx: Context) -> T {
+        Series::scalar(self.series().max_as_series())
+    }
+}
@@ -0,0 +1,111 @@
+use crate::prelude::*;
+
+pub mod sharable {
+    use crate::prelude::*;
+
+    use indexmap::map::MutableKeys;
+    use indexmap::IndexMap;
+
+    #[derive(
+        Clone, From, Deref, DerefMut, Finalize, Collectable, Debug, Send, Sync, Unpin, Trace,
+    )]
+    #[from(forward)]
+    pub struct Set<T: Sharable + Hash + Eq>(pub Gc<ConcreteSet<T>>);
+
+    /// A set is a map without values, so that its elements can be traced in place.
+    #[derive(Deref, DerefMut, Finalize, Collectable, Debug)]
+    pub struct ConcreteSet<T>(pub IndexMap<T, ()>);
+
+    unsafe impl<T: Trace + Hash + Eq> Trace for ConcreteSet<T> {
+        fn trace(&mut self, vis: &mut dyn Visitor) {
+            self.0.retain2(|x, _| {
+                x.trace(vis);
+                true
+            });
+        }
+    }
+
+    impl<T: Sharable + Hash + Eq> Alloc<Set<T>> for ConcreteSet<T> {
+        fn alloc(self, ctx: Context) -> Set<T> {
+            Set(ctx.mutator().allocate(self, AllocationSpace::New).into())
+        }
+    }
+}
+
+mod sendable {
+    use crate::prelude::*;
+
+    /// Elements are sent in insertion order, so the order survives conversions.
+    #[derive(Clone, From, Send, Serialize, Deserialize)]
+    #[serde(bound(serialize = "T: Hash + Eq", deserialize = "T: Hash + Eq"))]
+    #[from(forward)]
+    pub struct Set<T: Sendable>(pub ConcreteSet<T>);
+
+    pub type ConcreteSet<T> = indexmap::IndexSet<T>;
+}
+
+impl<T> DynSharable for sharable::Set<T>
+where
+    T: Sharable + Hash + Eq,
+    T::T: Sendable<T = T> + Hash + Eq,
+{
+    type T = sendable::Set<T::T>;
+    fn into_sendable(&self, ctx: Context) -> Self::T {
+        self.0
+            .keys()
+            .map(|x| x.into_sendable(ctx))
+            .collect::<sendable::ConcreteSet<_>>()
+            .into()
+    }
+    fn hollow(ctx: Context) -> std::option::Option<Self> {
+        Some(Set::new(ctx))
+    }
+}
+
+impl<T> DynSendable for sendable::Set<T>
+where
+    T: Sendable + Hash + Eq,
+    T::T: DynSharable<T = T> + Hash + Eq,
+{
+    type T = sharable::Set<T::T>;
+    fn into_sharable(&self, ctx: Context) -> Self::T {
+        let mut s = Set::<T::T>::new(ctx);
+        for x in self.0.iter() {
+            s.0.insert(x.into_sharable(ctx), ());
+        }
+        s
+    }
+}
+
+pub use sharable::Set;
+
+#[rewrite]
+impl<T: Sharable + Hash + Eq> Set<T> {
+    pub fn new(ctx: Context) -> Set<T> {
+        sharable::ConcreteSet(indexmap::IndexMap::new()).alloc(ctx)
+    }
+
+    /// Returns `true` if the element was not already in the set.
+    pub fn insert(mut self, value: T, ctx: Context) -> bool {
+        self.0.insert(value, ()).is_none()
+    }
+
+    /// Returns `true` if the element was in the set.
+    pub fn remove(mut self, value: T, ctx: Context) -> bool {
+        self.0.shift_remove(&value).is_some()
+    }
+
+    pub fn contains(self, value: T, ctx: Context) -> bool {
+        self.0.contains_key(&value)
+    }
+
+    pub fn len(self, ctx: Context) -> usize {
+        self.0.len()
+    }
+
+    /// Returns a snapshot of the elements in insertion order, so the set may be updated while
+    /// iterating over them.
+    pub fn iter(self, ctx: Context) -> Vec<T> {
+        Vec::from_iter(self.0.keys().cloned(), ctx)
+    }
+}
@@ -20,12 +20,42 @@ pub mod sharable {
             self.0.trace(vis)
         }
     }
+
+    /// Strings are compared and hashed by contents so that they can be used as keys.
+    impl PartialEq for String {
+        fn eq(&self, other: &Self) -> bool {
+            let (a, b): (&str, &str) = (&self.0, &other.0);
+            a == b
+        }
+    }
+
+    impl Eq for String {}
+
+    impl Hash for String {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            let s: &str = &self.0;
+            s.hash(state)
+        }
+    }
+
+    impl PartialOrd for String {
+        fn partial_cmp(&self, other: &Self) -> std::option::Option<std::cmp::Ordering> {
+            Some(Ord::cmp(self, other))
+        }
+    }
+
+    impl Ord for String {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            let (a, b): (&str, &str) = (&self.0, &other.0);
+            a.cmp(b)
+        }
+    }
 }
 
 mod sendable {
     use crate::prelude::*;
 
-    #[derive(Clone, From, Send, Serialize, Deserialize)]
+    #[derive(Clone, From, Send, Serialize, Deserialize, PartialEq, Eq, Hash)]
     #[from(forward)]
     pub struct String(pub ConcreteString);
 
@@ -37,6 +67,9 @@ impl DynSharable for sharable::String {
     fn into_sendable(&self, ctx: Context) -> Self::T {
         self.0.to_string().into()
     }
+    fn hollow(ctx: Context) -> std::option::Option<Self> {
+        Some(String::new(ctx))
+    }
 }
 
 impl DynSendable for sendable::String {
@@ -72,8 +105,15 @@ impl String {
         new.alloc(ctx)
     }
 
-    pub fn remove(mut self, idx: u32, _ctx: Context) -> char {
-        self.0.remove(idx as usize)
+    /// Removes the character at byte index `idx`, or returns `OptionNone` if `idx` is out of
+    /// bounds or not at a character boundary.
+    pub fn remove(mut self, idx: u32, ctx: Context) -> Option<char> {
+        let idx = idx as usize;
+        if idx < self.0.len() && self.as_str().is_char_boundary(idx) {
+            Option::some(self.0.remove(idx), ctx)
+        } else {
+            Option::none(ctx)
+        }
     }
 
     pub fn insert_char(mut self, idx: u32, ch: char, ctx: Context) {
@@ -112,4 +152,166 @@ impl String {
         new.0.push_str(ctx.mutator(), &other.0);
         new
     }
+
+    pub fn split(self, sep: String, ctx: Context) -> Vec<String> {
+        let parts = self.as_str().split(sep.as_str());
+        Vec::from_iter(parts.map(|part| String::from_str(part, ctx)), ctx)
+    }
+
+    pub fn trim(self, ctx: Context) -> String {
+        String::from_str(self.as_str().trim(), ctx)
+    }
+
+    pub fn trim_start(self, ctx: Context) -> String {
+        String::from_str(self.as_str().trim_start(), ctx)
+    }
+
+    pub fn trim_end(self, ctx: Context) -> String {
+        String::from_str(self.as_str().trim_end(), ctx)
+    }
+
+    /// Returns the char index of the first occurrence of `pat`, like the indices which
+    /// `substring` takes.
+    pub fn find(self, pat: String, ctx: Context) -> Option<u32> {
+        let s = self.as_str();
+        s.find(pat.as_str())
+            .map(|i| s[..i].chars().count() as u32)
+            .alloc(ctx)
+    }
+
+    pub fn replace(self, from: String, to: String, ctx: Context) -> String {
+        String::from_str(&self.as_str().replace(from.as_str(), to.as_str()), ctx)
+    }
+
+    pub fn contains(self, pat: String, ctx: Context) -> bool {
+        self.as_str().contains(pat.as_str())
+    }
+
+    pub fn starts_with(self, prefix: String, ctx: Context) -> bool {
+        self.as_str().starts_with(prefix.as_str())
+    }
+
+    pub fn ends_with(self, suffix: String, ctx: Context) -> bool {
+        self.as_str().ends_with(suffix.as_str())
+    }
+
+    pub fn to_uppercase(self, ctx: Context) -> String {
+        String::from_str(&self.as_str().to_uppercase(), ctx)
+    }
+
+    pub fn to_lowercase(self, ctx: Context) -> String {
+        String::from_str(&self.as_str().to_lowercase(), ctx)
+    }
+
+    /// Returns the characters from index `start` up to, but not including, index `end`, or
+    /// `OptionNone` if an index is out of bounds.
+    pub fn substring(self, start: u32, end: u32, ctx: Context) -> Option<String> {
+        let s = self.as_str();
+        let byte = |i: u32| {
+            s.char_indices()
+                .map(|(b, _)| b)
+                .chain(std::iter::once(s.len()))
+                .nth(i as usize)
+        };
+        byte(start)
+            .zip(byte(end.max(start)))
+            .map(|(start, end)| String::from_str(&s[start..end], ctx))
+            .alloc(ctx)
+    }
+
+    pub fn cmp(self, other: String, ctx: Context) -> i32 {
+        match Ord::cmp(&self, &other) {
+            std::cmp::Ordering::Less => -1,
+            std::cmp::Ordering::Equal => 0,
+            std::cmp::Ordering::Greater => 1,
+        }
+    }
+
+    pub fn lt(self, other: String, ctx: Context) -> bool {
+        self < other
+    }
+
+    pub fn hash(self, ctx: Context) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        Hash::hash(&self, &mut hasher);
+        std::hash::Hasher::finish(&hasher)
+    }
+
+    pub fn from_i8(i: i8, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_i16(i: i16, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_i64(i: i64, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_i128(i: i128, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_u8(i: u8, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_u16(i: u16, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_u32(i: u32, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_u64(i: u64, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_u128(i: u128, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_f32(i: f32, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_f64(i: f64, ctx: Context) -> String {
+        String::from_str(&i.to_string(), ctx)
+    }
+
+    pub fn from_bool(b: bool, ctx: Context) -> String {
+        String::from_str(&b.to_string(), ctx)
+    }
+
+    pub fn from_char(c: char, ctx: Context) -> String {
+        String::from_str(&c.to_string(), ctx)
+    }
 }
+
+macro_rules! parse {
+    ($name:ident, $ty:ty) => {
+        #[rewrite]
+        impl String {
+            /// Parses the string, or returns `OptionNone` if it is not a valid value.
+            pub fn $name(self, ctx: Context) -> Option<$ty> {
+                self.as_str().parse::<$ty>().ok().alloc(ctx)
+            }
+        }
+    };
+}
+
+parse!(parse_i8, i8);
+parse!(parse_i16, i16);
+parse!(parse_i32, i32);
+parse!(parse_i64, i64);
+parse!(parse_i128, i128);
+parse!(parse_u8, u8);
+parse!(parse_u16, u16);
+parse!(parse_u32, u32);
+parse!(parse_u64, u64);
+parse!(parse_u128, u128);
+parse!(parse_f32, f32);
+parse!(parse_f64, f64);
+parse!(parse_bool, bool);
@@ -0,0 +1,314 @@
+//! Timestamps and durations. Both are plain values which are shared and sent as they are.
+//! Comparisons use the standard operators, and arithmetic is available both through operators
+//! and through functions.
+
+use crate::data::garbage::alloc_identity;
+use crate::prelude::*;
+
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+/// A date and time of day without a time zone. Timestamps are in UTC by convention.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    From,
+    Serialize,
+    Deserialize,
+    Collectable,
+    Finalize,
+    NoTrace,
+)]
+#[serde(transparent)]
+pub struct DateTime(pub time::PrimitiveDateTime);
+
+/// A signed span of time with nanosecond precision.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    From,
+    Serialize,
+    Deserialize,
+    Collectable,
+    Finalize,
+    NoTrace,
+)]
+#[serde(transparent)]
+pub struct Duration(pub time::Duration);
+
+crate::data::convert_reflexive!(DateTime, hollow: DateTime::EPOCH);
+crate::data::convert_reflexive!(Duration, hollow: Duration::default());
+
+alloc_identity!(DateTime);
+alloc_identity!(Duration);
+
+impl DateTime {
+    /// The unix epoch, `1970-01-01 00:00:00`.
+    pub const EPOCH: DateTime = DateTime(time::macros::datetime!(1970-01-01 0:00));
+}
+
+impl Add<Duration> for DateTime {
+    type Output = DateTime;
+    fn add(self, rhs: Duration) -> DateTime {
+        DateTime(self.0 + rhs.0)
+    }
+}
+
+impl Sub<Duration> for DateTime {
+    type Output = DateTime;
+    fn sub(self, rhs: Duration) -> DateTime {
+        DateTime(self.0 - rhs.0)
+    }
+}
+
+impl Sub for DateTime {
+    type Output = Duration;
+    fn sub(self, rhs: DateTime) -> Duration {
+        Duration(self.0 - rhs.0)
+    }
+}
+
+impl AddAssign<Duration> for DateTime {
+    fn add_assign(&mut self, rhs: Duration) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign<Duration> for DateTime {
+    fn sub_assign(&mut self, rhs: Duration) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+    fn add(self, rhs: Duration) -> Duration {
+        Duration(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+    fn sub(self, rhs: Duration) -> Duration {
+        Duration(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Duration {
+    type Output = Duration;
+    fn neg(self) -> Duration {
+        Duration(-self.0)
+    }
+}
+
+impl Mul<i32> for Duration {
+    type Output = Duration;
+    fn mul(self, rhs: i32) -> Duration {
+        Duration(self.0 * rhs)
+    }
+}
+
+impl Div<i32> for Duration {
+    type Output = Duration;
+    fn div(self, rhs: i32) -> Duration {
+        Duration(self.0 / rhs)
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, rhs: Duration) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, rhs: Duration) {
+        self.0 -= rhs.0;
+    }
+}
+
+fn format_description(format: &str) -> std::vec::Vec<time::format_description::FormatItem<'_>> {
+    time::format_description::parse(format)
+        .unwrap_or_else(|e| panic!("Invalid format description {:?}: {}", format, e))
+}
+
+#[rewrite]
+impl DateTime {
+    /// The current time in UTC.
+    pub fn now(ctx: Context) -> DateTime {
+        let now = time::OffsetDateTime::now_utc();
+        DateTime(time::PrimitiveDateTime::new(now.date(), now.time()))
+    }
+
+    pub fn from_unix_timestamp(seconds: i64, ctx: Context) -> DateTime {
+        DateTime::EPOCH + Duration(time::Duration::seconds(seconds))
+    }
+
+    pub fn from_unix_timestamp_millis(milliseconds: i64, ctx: Context) -> DateTime {
+        DateTime::EPOCH + Duration(time::Duration::milliseconds(milliseconds))
+    }
+
+    pub fn unix_timestamp(self, ctx: Context) -> i64 {
+        (self - DateTime::EPOCH).0.whole_seconds()
+    }
+
+    pub fn unix_timestamp_millis(self, ctx: Context) -> i64 {
+        (self - DateTime::EPOCH).0.whole_milliseconds() as i64
+    }
+
+    /// Parses `s` according to a format description such as `[year]-[month]-[day]`, or returns
+    /// `OptionNone` if `s` does not match it.
+    pub fn parse(s: String, format: String, ctx: Context) -> Option<DateTime> {
+        let (s, format): (&str, &str) = (&s.0, &format.0);
+        time::PrimitiveDateTime::parse(s, &*format_description(format))
+            .ok()
+            .map(DateTime)
+            .alloc(ctx)
+    }
+
+    /// Formats the timestamp according to a format description such as `[year]-[month]-[day]`.
+    pub fn format(self, format: String, ctx: Context) -> String {
+        let format: &str = &format.0;
+        let s = self
+            .0
+            .format(&*format_description(format))
+            .unwrap_or_else(|e| panic!("Failed to format {} as {:?}: {}", self.0, format, e));
+        String::from_str(&s, ctx)
+    }
+
+    pub fn add(self, duration: Duration, ctx: Context) -> DateTime {
+        self + duration
+    }
+
+    pub fn sub(self, duration: Duration, ctx: Context) -> DateTime {
+        self - duration
+    }
+
+    /// Returns the duration from `other` until this timestamp.
+    pub fn since(self, other: DateTime, ctx: Context) -> Duration {
+        self - other
+    }
+
+    /// Rounds down to a multiple of `duration` since the epoch, which is the start of the
+    /// tumbling window of that length which contains the timestamp. Panics if `duration` is
+    /// not positive.
+    pub fn truncate(self, duration: Duration, ctx: Context) -> DateTime {
+        assert!(
+            duration.0.is_positive(),
+            "Cannot truncate to a duration of {}",
+            duration.0
+        );
+        let offset = (self - DateTime::EPOCH).0.whole_nanoseconds();
+        let offset = offset - offset.rem_euclid(duration.0.whole_nanoseconds());
+        // Nanoseconds overflow an `i64` about 292 years from the epoch, but seconds do not.
+        let seconds = i64::try_from(offset / 1_000_000_000)
+            .expect("Timestamps are within the range of an i64 of seconds");
+        let nanoseconds = (offset % 1_000_000_000) as i32;
+        DateTime::EPOCH + Duration(time::Duration::new(seconds, nanoseconds))
+    }
+
+    pub fn year(self, ctx: Context) -> i32 {
+        self.0.year()
+    }
+
+    pub fn month(self, ctx: Context) -> u8 {
+        self.0.month() as u8
+    }
+
+    pub fn day(self, ctx: Context) -> u8 {
+        self.0.day()
+    }
+
+    pub fn hour(self, ctx: Context) -> u8 {
+        self.0.hour()
+    }
+
+    pub fn minute(self, ctx: Context) -> u8 {
+        self.0.minute()
+    }
+
+    pub fn second(self, ctx: Context) -> u8 {
+        self.0.second()
+    }
+}
+
+#[rewrite]
+impl Duration {
+    pub fn from_seconds(seconds: i64, ctx: Context) -> Duration {
+        Duration(time::Duration::seconds(seconds))
+    }
+
+    pub fn from_seconds_f64(seconds: f64, ctx: Context) -> Duration {
+        Duration(time::Duration::seconds_f64(seconds))
+    }
+
+    pub fn from_milliseconds(milliseconds: i64, ctx: Context) -> Duration {
+        Duration(time::Duration::milliseconds(milliseconds))
+    }
+
+    pub fn from_microseconds(microseconds: i64, ctx: Context) -> Duration {
+        Duration(time::Duration::microseconds(microseconds))
+    }
+
+    pub fn from_nanoseconds(nanoseconds: i64, ctx: Context) -> Duration {
+        Duration(time::Duration::nanoseconds(nanoseconds))
+    }
+
+    pub fn whole_seconds(self, ctx: Context) -> i64 {
+        self.0.whole_seconds()
+    }
+
+    pub fn whole_milliseconds(self, ctx: Context) -> i64 {
+        self.0.whole_milliseconds() as i64
+    }
+
+    pub fn whole_microseconds(self, ctx: Context) -> i64 {
+        self.0.whole_microseconds() as i64
+    }
+
+    pub fn whole_nanoseconds(self, ctx: Context) -> i64 {
+        self.0.whole_nanoseconds() as i64
+    }
+
+    pub fn as_seconds_f64(self, ctx: Context) -> f64 {
+        self.0.as_seconds_f64()
+    }
+
+    pub fn add(self, other: Duration, ctx: Context) -> Duration {
+        self + other
+    }
+
+    pub fn sub(self, other: Duration, ctx: Context) -> Duration {
+        self - other
+    }
+
+    pub fn mul(self, factor: i32, ctx: Context) -> Duration {
+        self * factor
+    }
+
+    pub fn div(self, divisor: i32, ctx: Context) -> Duration {
+        self / divisor
+    }
+
+    pub fn abs(self, ctx: Context) -> Duration {
+        Duration(self.0.abs())
+    }
+}
@@ -0,0 +1,51 @@
+//! Tuples are converted element-wise, so a tuple can cross a channel whenever all of its
+//! elements can.
+
+use crate::prelude::*;
+
+macro_rules! impl_tuple {
+    ($($t:ident $i:tt),+) => {
+        impl<$($t: Sharable),+> DynSharable for ($($t,)+) {
+            type T = ($($t::T,)+);
+            fn into_sendable(&self, ctx: Context) -> Self::T {
+                ($(self.$i.into_sendable(ctx),)+)
+            }
+            fn hollow(ctx: Context) -> std::option::Option<Self> {
+                Some(($($t::hollow(ctx)?,)+))
+            }
+        }
+
+        impl<$($t: Sendable),+> DynSendable for ($($t,)+) {
+            type T = ($($t::T,)+);
+            fn into_sharable(&self, ctx: Context) -> Self::T {
+                ($(self.$i.into_sharable(ctx),)+)
+            }
+        }
+
+        impl<$($t: Tagged),+> Tagged for ($($t,)+) {
+            fn tag() -> std::string::String {
+                crate::data::erased::tag_of("Tuple", &[$($t::tag()),+])
+            }
+        }
+
+        impl<$($t),+> Alloc<($($t,)+)> for ($($t,)+) {
+            #[inline(always)]
+            fn alloc(self, _: Context) -> Self {
+                self
+            }
+        }
+    };
+}
+
+impl_tuple!(A0 0);
+impl_tuple!(A0 0, A1 1);
+impl_tuple!(A0 0, A1 1, A2 2);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6, A7 7);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6, A7 7, A8 8);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6, A7 7, A8 8, A9 9);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6, A7 7, A8 8, A9 9, A10 10);
+impl_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6, A7 7, A8 8, A9 9, A10 10, A11 11);
@@ -39,6 +39,9 @@ impl<T: Sharable> DynSharable for sharable::Vec<T> {
             .into_boxed_slice()
             .into()
     }
+    fn hollow(ctx: Context) -> std::option::Option<Self> {
+        Some(Vec::new(ctx))
+    }
 }
 
 impl<T: Sendable> DynSendable for sendable::Vec<T> {
@@ -56,6 +59,14 @@ impl<T: Sendable> DynSendable for sendable::Vec<T> {
 pub use sharable::Vec;
 
 impl<T: Sharable> Vec<T> {
+    pub fn from_iter(iter: impl IntoIterator<Item = T>, ctx: Context) -> Vec<T> {
+        let iter = iter.into_iter();
+        let mut v = Vec::with_capacity(iter.size_hint().0, ctx);
+        for x in iter {
+            v.0.push(ctx.mutator(), x);
+        }
+        v
+    }
     pub fn as_slice(&self, ctx: Context) -> &[T] {
         self.0.as_slice()
     }
@@ -116,15 +127,21 @@ impl<T: Sharable> Vec<T> {
     }
 
     pub fn pop(mut self, ctx: Context) -> Option<T> {
-        self.0.pop()
+        self.0.pop().alloc(ctx)
     }
 
-    pub fn remove(mut self, index: usize, ctx: Context) -> T {
-        self.0.remove(index)
+    /// Removes the element at `index`, or returns `OptionNone` if it is out of bounds.
+    pub fn remove(mut self, index: usize, ctx: Context) -> Option<T> {
+        if index < self.0.len() {
+            Option::some(self.0.remove(index), ctx)
+        } else {
+            Option::none(ctx)
+        }
     }
 
-    pub fn get(self, index: usize, ctx: Context) -> T {
-        self.0.at(index).clone()
+    /// Returns the element at `index`, or `OptionNone` if it is out of bounds.
+    pub fn get(self, index: usize, ctx: Context) -> Option<T> {
+        self.0.as_slice().get(index).cloned().alloc(ctx)
     }
 
     pub fn insert(mut self, index: usize, value: T, ctx: Context) {
@@ -134,6 +151,97 @@ impl<T: Sharable> Vec<T> {
     pub fn is_empty(self, ctx: Context) -> bool {
         self.0.is_empty()
     }
+
+    pub fn map<O: Sharable, F: Call<(T,), O>>(self, f: F, ctx: Context) -> Vec<O> {
+        let iter = self.0.iter().map(|x| f.call((x.clone(),), ctx));
+        Vec::from_iter(iter.collect::<std::vec::Vec<_>>(), ctx)
+    }
+
+    pub fn filter<F: Call<(T,), bool>>(self, f: F, ctx: Context) -> Vec<T> {
+        let iter = self
+            .0
+            .iter()
+            .filter(|x| f.call(((*x).clone(),), ctx))
+            .cloned();
+        Vec::from_iter(iter.collect::<std::vec::Vec<_>>(), ctx)
+    }
+
+    pub fn fold<A, F: Call<(A, T), A>>(self, init: A, f: F, ctx: Context) -> A {
+        self.0
+            .iter()
+            .fold(init, |acc, x| f.call((acc, x.clone()), ctx))
+    }
+
+    pub fn any<F: Call<(T,), bool>>(self, f: F, ctx: Context) -> bool {
+        self.0.iter().any(|x| f.call((x.clone(),), ctx))
+    }
+
+    pub fn all<F: Call<(T,), bool>>(self, f: F, ctx: Context) -> bool {
+        self.0.iter().all(|x| f.call((x.clone(),), ctx))
+    }
+
+    /// Returns the first element which satisfies `f`.
+    pub fn find<F: Call<(T,), bool>>(self, f: F, ctx: Context) -> Option<T> {
+        self.0
+            .iter()
+            .find(|x| f.call(((*x).clone(),), ctx))
+            .cloned()
+            .alloc(ctx)
+    }
+
+    /// Sorts the vector in place. `f` compares two elements and returns a negative number, zero
+    /// or a positive number if the first is less than, equal to or greater than the second.
+    ///
+    /// # Panics
+    ///
+    /// `f` must be a total order. If it is not, for example because it is inconsistent or
+    /// compares NaNs, the order of the elements is unspecified and sorting may panic.
+    pub fn sort_by<F: Call<(T, T), i32>>(mut self, f: F, ctx: Context) {
+        self.0
+            .as_slice_mut()
+            .sort_by(|a, b| f.call((a.clone(), b.clone()), ctx).cmp(&0));
+    }
+
+    /// Pairs up the elements of two vectors, stopping at the end of the shorter one.
+    pub fn zip<U: Sharable>(self, other: Vec<U>, ctx: Context) -> Vec<(T, U)> {
+        let iter = self.0.iter().cloned().zip(other.0.iter().cloned());
+        Vec::from_iter(iter.collect::<std::vec::Vec<_>>(), ctx)
+    }
+
+    pub fn concat(self, other: Vec<T>, ctx: Context) -> Vec<T> {
+        let iter = self.0.iter().chain(other.0.iter()).cloned();
+        Vec::from_iter(iter.collect::<std::vec::Vec<_>>(), ctx)
+    }
+
+    /// Returns a new vector with the elements in `[start, end)`, or `OptionNone` if the range is
+    /// out of bounds or `start` is after `end`.
+    pub fn slice(self, start: usize, end: usize, ctx: Context) -> Option<Vec<T>> {
+        self.0
+            .as_slice()
+            .get(start..end)
+            .map(|s| Vec::from_iter(s.to_vec(), ctx))
+            .alloc(ctx)
+    }
+
+    pub fn reverse(mut self, ctx: Context) {
+        self.0.as_slice_mut().reverse();
+    }
+
+    pub fn contains(self, value: T, ctx: Context) -> bool
+    where
+        T: PartialEq,
+    {
+        self.0.as_slice().contains(&value)
+    }
+
+    /// Searches a sorted vector for `value`. Returns its index if found, and otherwise the index
+    /// where it could be inserted to keep the vector sorted.
+    pub fn binary_search(self, value: T, ctx: Context) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.0.as_slice().binary_search(&value).alloc(ctx)
+    }
 }
 
 #[allow(non_snake_case)]
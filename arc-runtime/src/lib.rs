@@ -14,12 +14,16 @@
 #![allow(clippy::wrong_self_convention)]
 #![allow(clippy::len_without_is_empty)]
 
+extern crate self as arc_runtime;
+
 pub mod context;
 pub mod control;
 pub mod data;
 pub mod macros;
 pub mod operators;
 pub mod runtime;
+pub mod sink;
+pub mod source;
 pub mod task;
 
 pub mod prelude {
@@ -28,8 +32,13 @@ pub mod prelude {
     pub use crate::control::Control;
     pub use crate::control::Control::Continue;
     pub use crate::control::Control::Finished;
+    pub use crate::control::Control::Failed;
     pub use crate::data::channels;
     pub use crate::data::channels::Channel;
+    pub use crate::data::functions::Call;
+    pub use crate::data::functions::Code;
+    pub use crate::data::functions::ResolveError;
+    pub use crate::data::functions::SendableCode;
     pub use crate::data::garbage::Alloc;
     pub use crate::data::garbage::Gc;
     pub use crate::data::primitives::bool;
@@ -47,6 +56,7 @@ pub mod prelude {
     pub use crate::data::primitives::u64;
     pub use crate::data::primitives::u8;
     pub use crate::data::primitives::unit;
+    pub use crate::data::primitives::usize;
     pub use crate::data::primitives::Unit;
     pub use crate::data::Abstract;
     pub use crate::data::Concrete;
@@ -61,40 +71,286 @@ pub mod prelude {
     pub use crate::data::primitives::panic;
     pub use crate::data::primitives::print;
 
+    pub use crate::data::erased::Erased;
+    pub use crate::data::erased::Tagged;
+    pub use crate::data::format::format;
+    pub use crate::data::format::Interpolate;
+
+    pub use crate::data::primitives::char_from_u32;
+    pub use crate::data::primitives::char_is_alphabetic;
+    pub use crate::data::primitives::char_is_alphanumeric;
+    pub use crate::data::primitives::char_is_numeric;
+    pub use crate::data::primitives::char_is_whitespace;
+    pub use crate::data::primitives::char_to_ascii_lowercase;
+    pub use crate::data::primitives::char_to_ascii_uppercase;
+    pub use crate::data::primitives::char_to_string;
+    pub use crate::data::primitives::char_to_u32;
+
     pub use crate::data::strings::String;
     pub use crate::data::strings::String_clear;
+    pub use crate::data::strings::String_cmp;
+    pub use crate::data::strings::String_concat;
+    pub use crate::data::strings::String_contains;
+    pub use crate::data::strings::String_ends_with;
+    pub use crate::data::strings::String_eq;
+    pub use crate::data::strings::String_find;
+    pub use crate::data::strings::String_from_bool;
+    pub use crate::data::strings::String_from_char;
+    pub use crate::data::strings::String_from_f32;
+    pub use crate::data::strings::String_from_f64;
+    pub use crate::data::strings::String_from_i128;
+    pub use crate::data::strings::String_from_i16;
+    pub use crate::data::strings::String_from_i32;
+    pub use crate::data::strings::String_from_i64;
+    pub use crate::data::strings::String_from_i8;
     pub use crate::data::strings::String_from_str;
+    pub use crate::data::strings::String_from_u128;
+    pub use crate::data::strings::String_from_u16;
+    pub use crate::data::strings::String_from_u32;
+    pub use crate::data::strings::String_from_u64;
+    pub use crate::data::strings::String_from_u8;
+    pub use crate::data::strings::String_hash;
     pub use crate::data::strings::String_insert_char;
     pub use crate::data::strings::String_is_empty;
     pub use crate::data::strings::String_len;
+    pub use crate::data::strings::String_lt;
     pub use crate::data::strings::String_new;
+    pub use crate::data::strings::String_parse_bool;
+    pub use crate::data::strings::String_parse_f32;
+    pub use crate::data::strings::String_parse_f64;
+    pub use crate::data::strings::String_parse_i128;
+    pub use crate::data::strings::String_parse_i16;
+    pub use crate::data::strings::String_parse_i32;
+    pub use crate::data::strings::String_parse_i64;
+    pub use crate::data::strings::String_parse_i8;
+    pub use crate::data::strings::String_parse_u128;
+    pub use crate::data::strings::String_parse_u16;
+    pub use crate::data::strings::String_parse_u32;
+    pub use crate::data::strings::String_parse_u64;
+    pub use crate::data::strings::String_parse_u8;
     pub use crate::data::strings::String_push_char;
     pub use crate::data::strings::String_push_str;
     pub use crate::data::strings::String_remove;
+    pub use crate::data::strings::String_replace;
+    pub use crate::data::strings::String_split;
     pub use crate::data::strings::String_split_off;
+    pub use crate::data::strings::String_starts_with;
+    pub use crate::data::strings::String_substring;
+    pub use crate::data::strings::String_to_lowercase;
+    pub use crate::data::strings::String_to_uppercase;
+    pub use crate::data::strings::String_trim;
+    pub use crate::data::strings::String_trim_end;
+    pub use crate::data::strings::String_trim_start;
     pub use crate::data::strings::String_with_capacity;
-    pub use crate::data::strings::String_from_i32;
-    pub use crate::data::strings::String_eq;
-    pub use crate::data::strings::String_concat;
 
     pub use crate::data::vectors::Vec;
+    pub use crate::data::vectors::Vec_all;
+    pub use crate::data::vectors::Vec_any;
+    pub use crate::data::vectors::Vec_binary_search;
     pub use crate::data::vectors::Vec_capacity;
     pub use crate::data::vectors::Vec_clear;
+    pub use crate::data::vectors::Vec_concat;
+    pub use crate::data::vectors::Vec_contains;
     pub use crate::data::vectors::Vec_dedup;
+    pub use crate::data::vectors::Vec_filter;
+    pub use crate::data::vectors::Vec_find;
+    pub use crate::data::vectors::Vec_fold;
     pub use crate::data::vectors::Vec_get;
     pub use crate::data::vectors::Vec_insert;
     pub use crate::data::vectors::Vec_is_empty;
     pub use crate::data::vectors::Vec_len;
+    pub use crate::data::vectors::Vec_map;
     pub use crate::data::vectors::Vec_new;
     pub use crate::data::vectors::Vec_pop;
     pub use crate::data::vectors::Vec_push;
     pub use crate::data::vectors::Vec_remove;
+    pub use crate::data::vectors::Vec_reverse;
+    pub use crate::data::vectors::Vec_slice;
+    pub use crate::data::vectors::Vec_sort_by;
     pub use crate::data::vectors::Vec_with_capacity;
+    pub use crate::data::vectors::Vec_zip;
+
+    pub use crate::data::options::sharable_Option::ConcreteOption::OptionNone;
+    pub use crate::data::options::sharable_Option::ConcreteOption::OptionSome;
+    pub use crate::data::options::sharable_Option::Option;
+    pub use crate::data::options::Option_and_then;
+    pub use crate::data::options::Option_is_none;
+    pub use crate::data::options::Option_is_some;
+    pub use crate::data::options::Option_map;
+    pub use crate::data::options::Option_none;
+    pub use crate::data::options::Option_some;
+    pub use crate::data::options::Option_unwrap;
+    pub use crate::data::options::Option_unwrap_or;
+
+    pub use crate::data::results::sharable_Result::ConcreteResult::ResultErr;
+    pub use crate::data::results::sharable_Result::ConcreteResult::ResultOk;
+    pub use crate::data::results::sharable_Result::Result;
+    pub use crate::data::results::Result_and_then;
+    pub use crate::data::results::Result_err;
+    pub use crate::data::results::Result_is_err;
+    pub use crate::data::results::Result_is_ok;
+    pub use crate::data::results::Result_map;
+    pub use crate::data::results::Result_ok;
+    pub use crate::data::results::Result_unwrap_or;
+
+    pub use crate::data::bigints::BigInt;
+    pub use crate::data::bigints::BigInt_abs;
+    pub use crate::data::bigints::BigInt_add;
+    pub use crate::data::bigints::BigInt_div;
+    pub use crate::data::bigints::BigInt_eq;
+    pub use crate::data::bigints::BigInt_from_i64;
+    pub use crate::data::bigints::BigInt_gt;
+    pub use crate::data::bigints::BigInt_gt_eq;
+    pub use crate::data::bigints::BigInt_lt;
+    pub use crate::data::bigints::BigInt_lt_eq;
+    pub use crate::data::bigints::BigInt_mul;
+    pub use crate::data::bigints::BigInt_neg;
+    pub use crate::data::bigints::BigInt_neq;
+    pub use crate::data::bigints::BigInt_parse;
+    pub use crate::data::bigints::BigInt_pow;
+    pub use crate::data::bigints::BigInt_rem;
+    pub use crate::data::bigints::BigInt_signum;
+    pub use crate::data::bigints::BigInt_sub;
+    pub use crate::data::bigints::BigInt_to_i64;
+    pub use crate::data::bigints::BigInt_to_string;
+
+    pub use crate::data::bytes::Bytes;
+    pub use crate::data::bytes::Bytes_concat;
+    pub use crate::data::bytes::Bytes_eq;
+    pub use crate::data::bytes::Bytes_from_base64;
+    pub use crate::data::bytes::Bytes_from_hex;
+    pub use crate::data::bytes::Bytes_from_string;
+    pub use crate::data::bytes::Bytes_from_vec;
+    pub use crate::data::bytes::Bytes_get;
+    pub use crate::data::bytes::Bytes_is_empty;
+    pub use crate::data::bytes::Bytes_len;
+    pub use crate::data::bytes::Bytes_new;
+    pub use crate::data::bytes::Bytes_slice;
+    pub use crate::data::bytes::Bytes_to_base64;
+    pub use crate::data::bytes::Bytes_to_hex;
+    pub use crate::data::bytes::Bytes_to_string;
+    pub use crate::data::bytes::Bytes_to_vec;
+
+    pub use crate::data::decimals::Decimal;
+    pub use crate::data::decimals::Decimal_abs;
+    pub use crate::data::decimals::Decimal_add;
+    pub use crate::data::decimals::Decimal_div;
+    pub use crate::data::decimals::Decimal_eq;
+    pub use crate::data::decimals::Decimal_from_f64;
+    pub use crate::data::decimals::Decimal_from_i64;
+    pub use crate::data::decimals::Decimal_gt;
+    pub use crate::data::decimals::Decimal_gt_eq;
+    pub use crate::data::decimals::Decimal_lt;
+    pub use crate::data::decimals::Decimal_lt_eq;
+    pub use crate::data::decimals::Decimal_mul;
+    pub use crate::data::decimals::Decimal_neg;
+    pub use crate::data::decimals::Decimal_neq;
+    pub use crate::data::decimals::Decimal_parse;
+    pub use crate::data::decimals::Decimal_round;
+    pub use crate::data::decimals::Decimal_scale;
+    pub use crate::data::decimals::Decimal_sub;
+    pub use crate::data::decimals::Decimal_to_f64;
+    pub use crate::data::decimals::Decimal_to_string;
+
+    pub use crate::data::records::Close;
+    pub use crate::data::records::Open;
+
+    pub use crate::data::maps::Map;
+    pub use crate::data::maps::Map_contains;
+    pub use crate::data::maps::Map_get;
+    pub use crate::data::maps::Map_insert;
+    pub use crate::data::maps::Map_iter;
+    pub use crate::data::maps::Map_len;
+    pub use crate::data::maps::Map_new;
+    pub use crate::data::maps::Map_remove;
+
+    pub use crate::data::sets::Set;
+    pub use crate::data::sets::Set_contains;
+    pub use crate::data::sets::Set_insert;
+    pub use crate::data::sets::Set_iter;
+    pub use crate::data::sets::Set_len;
+    pub use crate::data::sets::Set_new;
+    pub use crate::data::sets::Set_remove;
+
+    pub use crate::data::times::DateTime;
+    pub use crate::data::times::DateTime_add;
+    pub use crate::data::times::DateTime_day;
+    pub use crate::data::times::DateTime_format;
+    pub use crate::data::times::DateTime_from_unix_timestamp;
+    pub use crate::data::times::DateTime_from_unix_timestamp_millis;
+    pub use crate::data::times::DateTime_hour;
+    pub use crate::data::times::DateTime_minute;
+    pub use crate::data::times::DateTime_month;
+    pub use crate::data::times::DateTime_now;
+    pub use crate::data::times::DateTime_parse;
+    pub use crate::data::times::DateTime_second;
+    pub use crate::data::times::DateTime_since;
+    pub use crate::data::times::DateTime_sub;
+    pub use crate::data::times::DateTime_truncate;
+    pub use crate::data::times::DateTime_unix_timestamp;
+    pub use crate::data::times::DateTime_unix_timestamp_millis;
+    pub use crate::data::times::DateTime_year;
+
+    pub use crate::data::times::Duration;
+    pub use crate::data::times::Duration_abs;
+    pub use crate::data::times::Duration_add;
+    pub use crate::data::times::Duration_as_seconds_f64;
+    pub use crate::data::times::Duration_div;
+    pub use crate::data::times::Duration_from_microseconds;
+    pub use crate::data::times::Duration_from_milliseconds;
+    pub use crate::data::times::Duration_from_nanoseconds;
+    pub use crate::data::times::Duration_from_seconds;
+    pub use crate::data::times::Duration_from_seconds_f64;
+    pub use crate::data::times::Duration_mul;
+    pub use crate::data::times::Duration_sub;
+    pub use crate::data::times::Duration_whole_microseconds;
+    pub use crate::data::times::Duration_whole_milliseconds;
+    pub use crate::data::times::Duration_whole_nanoseconds;
+    pub use crate::data::times::Duration_whole_seconds;
+
+    #[cfg(feature = "arrow")]
+    pub use crate::data::arrow::Arrow;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_column;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_filter;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_from_series;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_group_by_agg;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_head;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_height;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_join;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_new;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_read_csv;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_select;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_sort;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::dataframe::DataFrame_to_vec;
+    #[cfg(feature = "dataframes")]
+    pub use crate::data::series::{
+        Element, Series, Series_add, Series_div, Series_eq, Series_from_vec, Series_get, Series_gt,
+        Series_gt_eq, Series_len, Series_lt, Series_lt_eq, Series_max, Series_mean, Series_min,
+        Series_mul, Series_name, Series_neq, Series_sub, Series_sum, Series_to_vec,
+    };
 
     pub use crate::data::cells::Cell;
+    pub use crate::data::cells::Cell_compare_and_set;
     pub use crate::data::cells::Cell_get;
     pub use crate::data::cells::Cell_new;
+    pub use crate::data::cells::Cell_replace;
     pub use crate::data::cells::Cell_set;
+    pub use crate::data::cells::Cell_swap;
+    pub use crate::data::cells::Cell_update;
 
     // Declarative macros
     pub use crate::access;
@@ -112,6 +368,8 @@ pub mod prelude {
     pub use macros::call;
     pub use macros::call_async;
     pub use macros::call_indirect;
+    pub use macros::closure;
+    pub use macros::declare_records;
     pub use macros::enwrap;
     pub use macros::erase;
     pub use macros::is;
@@ -120,6 +378,7 @@ pub mod prelude {
     pub use macros::pull_transition;
     pub use macros::push;
     pub use macros::push_transition;
+    pub use macros::record;
     pub use macros::rewrite;
     pub use macros::terminate;
     pub use macros::transition;
@@ -140,6 +399,7 @@ pub mod prelude {
     pub use macros::Unpin;
 
     // Re-exports
+    pub use kompact::prelude::error;
     pub use kompact::prelude::info;
     pub use kompact::prelude::warn;
     pub use kompact::prelude::Actor;
@@ -184,8 +444,6 @@ pub mod prelude {
 
     pub use time::macros::date;
     pub use time::macros::time;
-    pub use time::Duration;
-    pub use time::PrimitiveDateTime as DateTime;
 
     pub use futures::future::BoxFuture;
     pub use futures::future::FutureExt;
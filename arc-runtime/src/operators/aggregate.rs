@@ -0,0 +1,291 @@
+//! Composable aggregators for the `reduce` clause of queries.
+//!
+//! An aggregator is constructed once per operator and then folds the elements of every group
+//! into an accumulator. Tuples of aggregators are themselves aggregators, which is how
+//! `reduce sum of x.v, count` computes several aggregates in a single pass.
+
+use crate::data::AsyncSafe;
+use crate::prelude::*;
+
+use std::marker::PhantomData;
+use std::ops::Add;
+
+pub trait Aggregator<I>: Clone + AsyncSafe {
+    type Acc: Clone + AsyncSafe;
+    type Out;
+    fn init(&self, ctx: Context) -> Self::Acc;
+    fn update(&self, acc: Self::Acc, input: I, ctx: Context) -> Self::Acc;
+    fn finish(&self, acc: Self::Acc, ctx: Context) -> Self::Out;
+}
+
+/// Numbers which can be averaged. Unlike `Into<f64>`, this also covers 64-bit integers, which
+/// are converted to the nearest `f64`.
+pub trait AsF64 {
+    fn as_f64(&self) -> f64;
+}
+
+macro_rules! impl_as_f64 {
+    ($($ty:ty),+) => {
+        $(impl AsF64 for $ty {
+            fn as_f64(&self) -> f64 {
+                *self as f64
+            }
+        })+
+    };
+}
+
+impl_as_f64!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Counts the number of elements.
+#[derive(Clone, Copy, Debug)]
+pub struct Count;
+
+/// Sums the values extracted from each element.
+#[derive(Clone)]
+pub struct Sum<F, V>(F, PhantomData<V>);
+
+/// The smallest value extracted from any element.
+#[derive(Clone)]
+pub struct Min<F, V>(F, PhantomData<V>);
+
+/// The largest value extracted from any element.
+#[derive(Clone)]
+pub struct Max<F, V>(F, PhantomData<V>);
+
+/// The arithmetic mean of the values extracted from each element.
+#[derive(Clone)]
+pub struct Avg<F, V>(F, PhantomData<V>);
+
+/// The value extracted from the first element.
+#[derive(Clone)]
+pub struct First<F, V>(F, PhantomData<V>);
+
+/// The value extracted from the last element.
+#[derive(Clone)]
+pub struct Last<F, V>(F, PhantomData<V>);
+
+/// A user-defined aggregator which folds each element into `init` with a function value.
+#[derive(Clone)]
+pub struct Fold<F, A>(A, F);
+
+pub fn count(_: Context) -> Count {
+    Count
+}
+
+pub fn sum<F, V>(f: F, _: Context) -> Sum<F, V> {
+    Sum(f, PhantomData)
+}
+
+pub fn min<F, V>(f: F, _: Context) -> Min<F, V> {
+    Min(f, PhantomData)
+}
+
+pub fn max<F, V>(f: F, _: Context) -> Max<F, V> {
+    Max(f, PhantomData)
+}
+
+pub fn avg<F, V>(f: F, _: Context) -> Avg<F, V> {
+    Avg(f, PhantomData)
+}
+
+pub fn first<F, V>(f: F, _: Context) -> First<F, V> {
+    First(f, PhantomData)
+}
+
+pub fn last<F, V>(f: F, _: Context) -> Last<F, V> {
+    Last(f, PhantomData)
+}
+
+pub fn fold<F, A>(init: A, f: F, _: Context) -> Fold<F, A> {
+    Fold(init, f)
+}
+
+impl<I> Aggregator<I> for Count {
+    type Acc = u64;
+    type Out = u64;
+    fn init(&self, _: Context) -> u64 {
+        0
+    }
+    fn update(&self, acc: u64, _: I, _: Context) -> u64 {
+        acc + 1
+    }
+    fn finish(&self, acc: u64, _: Context) -> u64 {
+        acc
+    }
+}
+
+impl<I, F, V> Aggregator<I> for Sum<F, V>
+where
+    F: Call<(I,), V>,
+    V: Add<Output = V> + Default + Clone + AsyncSafe,
+{
+    type Acc = V;
+    type Out = V;
+    fn init(&self, _: Context) -> V {
+        V::default()
+    }
+    fn update(&self, acc: V, input: I, ctx: Context) -> V {
+        acc + self.0.call((input,), ctx)
+    }
+    fn finish(&self, acc: V, _: Context) -> V {
+        acc
+    }
+}
+
+impl<I, F, V> Aggregator<I> for Min<F, V>
+where
+    F: Call<(I,), V>,
+    V: PartialOrd + Clone + AsyncSafe,
+{
+    type Acc = std::option::Option<V>;
+    type Out = V;
+    fn init(&self, _: Context) -> std::option::Option<V> {
+        None
+    }
+    fn update(
+        &self,
+        acc: std::option::Option<V>,
+        input: I,
+        ctx: Context,
+    ) -> std::option::Option<V> {
+        let v = self.0.call((input,), ctx);
+        match acc {
+            Some(acc) if acc <= v => Some(acc),
+            _ => Some(v),
+        }
+    }
+    fn finish(&self, acc: std::option::Option<V>, _: Context) -> V {
+        acc.expect("Aggregated an empty group")
+    }
+}
+
+impl<I, F, V> Aggregator<I> for Max<F, V>
+where
+    F: Call<(I,), V>,
+    V: PartialOrd + Clone + AsyncSafe,
+{
+    type Acc = std::option::Option<V>;
+    type Out = V;
+    fn init(&self, _: Context) -> std::option::Option<V> {
+        None
+    }
+    fn update(
+        &self,
+        acc: std::option::Option<V>,
+        input: I,
+        ctx: Context,
+    ) -> std::option::Option<V> {
+        let v = self.0.call((input,), ctx);
+        match acc {
+            Some(acc) if acc >= v => Some(acc),
+            _ => Some(v),
+        }
+    }
+    fn finish(&self, acc: std::option::Option<V>, _: Context) -> V {
+        acc.expect("Aggregated an empty group")
+    }
+}
+
+impl<I, F, V> Aggregator<I> for Avg<F, V>
+where
+    F: Call<(I,), V>,
+    V: AsF64 + Clone + AsyncSafe,
+{
+    type Acc = (f64, u64);
+    type Out = f64;
+    fn init(&self, _: Context) -> (f64, u64) {
+        (0.0, 0)
+    }
+    fn update(&self, (sum, n): (f64, u64), input: I, ctx: Context) -> (f64, u64) {
+        (sum + self.0.call((input,), ctx).as_f64(), n + 1)
+    }
+    fn finish(&self, (sum, n): (f64, u64), _: Context) -> f64 {
+        sum / n as f64
+    }
+}
+
+impl<I, F, V> Aggregator<I> for First<F, V>
+where
+    F: Call<(I,), V>,
+    V: Clone + AsyncSafe,
+{
+    type Acc = std::option::Option<V>;
+    type Out = V;
+    fn init(&self, _: Context) -> std::option::Option<V> {
+        None
+    }
+    fn update(
+        &self,
+        acc: std::option::Option<V>,
+        input: I,
+        ctx: Context,
+    ) -> std::option::Option<V> {
+        acc.or_else(|| Some(self.0.call((input,), ctx)))
+    }
+    fn finish(&self, acc: std::option::Option<V>, _: Context) -> V {
+        acc.expect("Aggregated an empty group")
+    }
+}
+
+impl<I, F, V> Aggregator<I> for Last<F, V>
+where
+    F: Call<(I,), V>,
+    V: Clone + AsyncSafe,
+{
+    type Acc = std::option::Option<V>;
+    type Out = V;
+    fn init(&self, _: Context) -> std::option::Option<V> {
+        None
+    }
+    fn update(&self, _: std::option::Option<V>, input: I, ctx: Context) -> std::option::Option<V> {
+        Some(self.0.call((input,), ctx))
+    }
+    fn finish(&self, acc: std::option::Option<V>, _: Context) -> V {
+        acc.expect("Aggregated an empty group")
+    }
+}
+
+impl<I, F, A> Aggregator<I> for Fold<F, A>
+where
+    F: Call<(A, I), A>,
+    A: Clone + AsyncSafe,
+{
+    type Acc = A;
+    type Out = A;
+    fn init(&self, _: Context) -> A {
+        self.0.clone()
+    }
+    fn update(&self, acc: A, input: I, ctx: Context) -> A {
+        self.1.call((acc, input), ctx)
+    }
+    fn finish(&self, acc: A, _: Context) -> A {
+        acc
+    }
+}
+
+macro_rules! impl_aggregator_tuple {
+    ($($a:ident $i:tt),+) => {
+        impl<I: Clone, $($a: Aggregator<I>),+> Aggregator<I> for ($($a,)+) {
+            type Acc = ($($a::Acc,)+);
+            type Out = ($($a::Out,)+);
+            fn init(&self, ctx: Context) -> Self::Acc {
+                ($(self.$i.init(ctx),)+)
+            }
+            fn update(&self, acc: Self::Acc, input: I, ctx: Context) -> Self::Acc {
+                ($(self.$i.update(acc.$i, input.clone(), ctx),)+)
+            }
+            fn finish(&self, acc: Self::Acc, ctx: Context) -> Self::Out {
+                ($(self.$i.finish(acc.$i, ctx),)+)
+            }
+        }
+    };
+}
+
+impl_aggregator_tuple!(A0 0);
+impl_aggregator_tuple!(A0 0, A1 1);
+impl_aggregator_tuple!(A0 0, A1 1, A2 2);
+impl_aggregator_tuple!(A0 0, A1 1, A2 2, A3 3);
+impl_aggregator_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4);
+impl_aggregator_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5);
+impl_aggregator_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6);
+impl_aggregator_tuple!(A0 0, A1 1, A2 2, A3 3, A4 4, A5 5, A6 6, A7 7);
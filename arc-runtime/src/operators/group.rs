@@ -0,0 +1,135 @@
+//! The `group ... reduce` operator.
+
+use crate::data::channels::local::multicast as clm;
+use crate::operators::aggregate::Aggregator;
+use crate::operators::window::Emit;
+use crate::operators::window::Windows;
+use crate::prelude::*;
+
+use std::collections::HashMap;
+
+/// Aggregation state of every group, kept in the order in which keys were first seen.
+pub(crate) struct Groups<K, I, A: Aggregator<I>> {
+    agg: A,
+    index: HashMap<K, usize>,
+    groups: std::vec::Vec<(K, A::Acc)>,
+}
+
+impl<K: Hash + Eq + Clone, I, A: Aggregator<I>> Groups<K, I, A> {
+    pub(crate) fn new(agg: A) -> Self {
+        Self {
+            agg,
+            index: HashMap::new(),
+            groups: std::vec::Vec::new(),
+        }
+    }
+
+    /// Folds `input` into the group of `key` and returns the position of that group.
+    pub(crate) fn update(&mut self, key: K, input: I, ctx: Context) -> usize {
+        let i = match self.index.get(&key) {
+            Some(i) => *i,
+            None => {
+                self.index.insert(key.clone(), self.groups.len());
+                self.groups.push((key, self.agg.init(ctx)));
+                self.groups.len() - 1
+            }
+        };
+        let acc = self.groups[i].1.clone();
+        self.groups[i].1 = self.agg.update(acc, input, ctx);
+        i
+    }
+
+    pub(crate) fn result(&self, i: usize, ctx: Context) -> (K, A::Out) {
+        let (key, acc) = &self.groups[i];
+        (key.clone(), self.agg.finish(acc.clone(), ctx))
+    }
+
+    /// Removes all groups and returns their results.
+    pub(crate) fn drain(&mut self, ctx: Context) -> std::vec::Vec<(K, A::Out)> {
+        self.index.clear();
+        let agg = &self.agg;
+        self.groups
+            .drain(..)
+            .map(|(key, acc)| (key, agg.finish(acc, ctx)))
+            .collect()
+    }
+}
+
+impl<I: Sharable> Vec<I> {
+    /// Groups the elements by `key`, aggregates each group with `agg`, and builds one output
+    /// per group with `output`. Groups are returned in the order their keys were first seen.
+    pub fn group_reduce<K, A, F, G, O>(self, key: F, agg: A, output: G, ctx: Context) -> Vec<O>
+    where
+        K: Hash + Eq + Clone,
+        A: Aggregator<I>,
+        F: Call<(I,), K>,
+        G: Call<(K, A::Out), O>,
+        O: Sharable,
+    {
+        let mut groups = Groups::new(agg);
+        for x in self.0.iter() {
+            groups.update(key.call((x.clone(),), ctx), x.clone(), ctx);
+        }
+        let results = groups.drain(ctx);
+        Vec::from_iter(results.into_iter().map(|r| output.call(r, ctx)), ctx)
+    }
+}
+
+impl<I: Sharable> clm::Pullable<I>
+where
+    I::T: Sendable<T = I>,
+{
+    /// Groups the stream by `key` and aggregates each group with `agg`. Results are built with
+    /// `output` and pushed as decided by `emit`. Windowed results of all groups are emitted in
+    /// the order their keys were first seen in the window, and are flushed when the input ends.
+    pub fn group_reduce<K, A, F, G, O>(
+        mut self,
+        key: F,
+        agg: A,
+        emit: Emit<I>,
+        output: G,
+        ctx: Context,
+    ) -> clm::Pullable<O>
+    where
+        I: 'static,
+        K: Hash + Eq + Clone + 'static,
+        A: Aggregator<I> + 'static,
+        F: Call<(I,), K> + 'static,
+        G: Call<(K, A::Out), O> + 'static,
+        O: Sharable + 'static,
+    {
+        let (o0, o1) = clm::channel(ctx);
+        ctx.spawn(move |ctx| async move {
+            let mut groups = Groups::new(agg);
+            let mut windows = Windows::new(emit);
+            loop {
+                let x = match self.pull(ctx).await {
+                    Control::Continue(x) => x,
+                    Control::Finished => break,
+                    Control::Failed(error) => return o0.fail(error).await,
+                };
+                if windows.closes_before(&x, ctx) {
+                    for (k, r) in groups.drain(ctx) {
+                        o0.push(output.call((k, r), ctx), ctx).await?;
+                    }
+                }
+                let i = groups.update(key.call((x.clone(),), ctx), x, ctx);
+                if windows.is_incremental() {
+                    let (k, r) = groups.result(i, ctx);
+                    o0.push(output.call((k, r), ctx), ctx).await?;
+                } else if windows.closes_after() {
+                    for (k, r) in groups.drain(ctx) {
+                        o0.push(output.call((k, r), ctx), ctx).await?;
+                    }
+                }
+            }
+            if !windows.is_incremental() {
+                for (k, r) in groups.drain(ctx) {
+                    o0.push(output.call((k, r), ctx), ctx).await?;
+                }
+            }
+            Control::Finished
+        });
+        o1
+    }
+}
@@ -1,6 +1,11 @@
 //! Builtin streaming operators. Everything required to support the SQL-interface.
 #![allow(clippy::type_complexity)]
 
+pub mod aggregate;
+pub mod group;
+pub mod order;
+pub mod window;
+
 use crate::data::Sharable;
 use crate::prelude::*;
 
@@ -9,14 +14,6 @@ use crate::data::channels::local::multicast as clm;
 // use crate::channels::local::data_parallel as clt;
 // use crate::channels::local::window as clw;
 
-use crate::prelude::DateTime;
-
-use rand::distributions::Distribution;
-use rand::distributions::Standard;
-use rand::Rng;
-
-use std::marker::PhantomData;
-
 use kompact::prelude::*;
 
 use std::sync::Arc;
@@ -58,39 +55,3 @@ impl<I: Sharable> clm::Pullable<I> {
 //         todo!()
 //     }
 }
-
-pub struct DataGen<T> {
-    offset: i64,
-    count: usize,
-    rng: rand::prelude::ThreadRng,
-    marker: PhantomData<T>,
-}
-
-impl<T> DataGen<T> {
-    fn new(count: usize) -> Self {
-        Self {
-            offset: 0,
-            count,
-            rng: rand::prelude::thread_rng(),
-            marker: PhantomData,
-        }
-    }
-}
-
-impl<T: Sharable> Iterator for DataGen<T>
-where
-    Standard: Distribution<T>,
-{
-    type Item = (DateTime, T);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.offset += 1;
-        self.count -= 1;
-        if self.count > 0 {
-            todo!()
-        //             Some((DateTime::from_unix_timestamp(self.offset), self.rng.gen()))
-        } else {
-            None
-        }
-    }
-}
@@ -0,0 +1,274 @@
+//! The `order by` and top-k operators.
+
+use crate::data::channels::local::multicast as clm;
+use crate::operators::window::Emit;
+use crate::operators::window::Windows;
+use crate::prelude::*;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// The direction in which elements are ordered by their key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// A sort key, which must be totally ordered so that ranking is well defined. Floats are
+/// ordered by [`f64::total_cmp`], which places `NaN` after all other values.
+pub trait Key {
+    fn compare(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_key {
+    { $($ty:ty),* } => {
+        $(
+            impl Key for $ty {
+                fn compare(&self, other: &Self) -> Ordering {
+                    Ord::cmp(self, other)
+                }
+            }
+        )*
+    }
+}
+
+impl_key!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, bool, char, unit, String,
+    DateTime, Duration, BigInt, Decimal
+);
+
+impl Key for f32 {
+    fn compare(&self, other: &Self) -> Ordering {
+        self.total_cmp(other)
+    }
+}
+
+impl Key for f64 {
+    fn compare(&self, other: &Self) -> Ordering {
+        self.total_cmp(other)
+    }
+}
+
+impl<A: Key, B: Key> Key for (A, B) {
+    fn compare(&self, other: &Self) -> Ordering {
+        self.0
+            .compare(&other.0)
+            .then_with(|| self.1.compare(&other.1))
+    }
+}
+
+/// An element together with its sort key. Entries are ranked by key in the direction of
+/// `order`, and ties are broken by arrival so that sorting is stable.
+struct Ranked<K, I> {
+    key: K,
+    seq: u64,
+    order: Order,
+    item: I,
+}
+
+impl<K: Key, I> Ord for Ranked<K, I> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ord = self.key.compare(&other.key);
+        let ord = match self.order {
+            Order::Asc => ord,
+            Order::Desc => ord.reverse(),
+        };
+        ord.then(self.seq.cmp(&other.seq))
+    }
+}
+
+impl<K: Key, I> PartialOrd for Ranked<K, I> {
+    fn partial_cmp(&self, other: &Self) -> std::option::Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Key, I> PartialEq for Ranked<K, I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Key, I> Eq for Ranked<K, I> {}
+
+/// Elements of a window ordered by rank. If a `limit` is set, then only that many of the
+/// best-ranked elements are retained. The heap is a max-heap, so its top is always the
+/// worst-ranked element, which is the one to evict.
+pub(crate) struct Ranking<K, I> {
+    heap: BinaryHeap<Ranked<K, I>>,
+    order: Order,
+    limit: std::option::Option<usize>,
+    seq: u64,
+}
+
+impl<K: Key, I: Clone> Ranking<K, I> {
+    pub(crate) fn new(order: Order, limit: std::option::Option<usize>) -> Self {
+        Self {
+            heap: BinaryHeap::with_capacity(limit.map(|k| k + 1).unwrap_or(0)),
+            order,
+            limit,
+            seq: 0,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, key: K, item: I) {
+        let entry = Ranked {
+            key,
+            seq: self.seq,
+            order: self.order,
+            item,
+        };
+        self.seq += 1;
+        match self.limit {
+            Some(0) => {}
+            Some(k) if self.heap.len() >= k => {
+                if let Some(mut worst) = self.heap.peek_mut() {
+                    if entry < *worst {
+                        *worst = entry;
+                    }
+                }
+            }
+            _ => self.heap.push(entry),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the retained elements from best to worst rank.
+    pub(crate) fn sorted(&self) -> std::vec::Vec<I> {
+        let mut entries = self.heap.iter().collect::<std::vec::Vec<_>>();
+        entries.sort();
+        entries.into_iter().map(|e| e.item.clone()).collect()
+    }
+
+    /// Removes and returns the retained elements from best to worst rank.
+    pub(crate) fn drain(&mut self) -> std::vec::Vec<I> {
+        self.seq = 0;
+        std::mem::take(&mut self.heap)
+            .into_sorted_vec()
+            .into_iter()
+            .map(|e| e.item)
+            .collect()
+    }
+}
+
+impl<I: Sharable> Vec<I> {
+    /// Returns a new vector with the elements stably sorted by `key`.
+    pub fn order_by<K, F>(self, key: F, order: Order, ctx: Context) -> Vec<I>
+    where
+        K: Key,
+        F: Call<(I,), K>,
+    {
+        self.rank(key, order, None, ctx)
+    }
+
+    /// Returns a new vector with the `k` first elements as sorted by `key`.
+    pub fn top_k<K, F>(self, key: F, order: Order, k: usize, ctx: Context) -> Vec<I>
+    where
+        K: Key,
+        F: Call<(I,), K>,
+    {
+        self.rank(key, order, Some(k), ctx)
+    }
+
+    fn rank<K, F>(
+        self,
+        key: F,
+        order: Order,
+        limit: std::option::Option<usize>,
+        ctx: Context,
+    ) -> Vec<I>
+    where
+        K: Key,
+        F: Call<(I,), K>,
+    {
+        let mut ranking = Ranking::new(order, limit);
+        for x in self.0.iter() {
+            ranking.insert(key.call((x.clone(),), ctx), x.clone());
+        }
+        Vec::from_iter(ranking.drain(), ctx)
+    }
+}
+
+impl<I: Sharable> clm::Pullable<I>
+where
+    I::T: Sendable<T = I>,
+{
+    /// Sorts each window of the stream by `key` and emits it as a vector.
+    pub fn order_by<K, F>(
+        self,
+        key: F,
+        order: Order,
+        emit: Emit<I>,
+        ctx: Context,
+    ) -> clm::Pullable<Vec<I>>
+    where
+        I: 'static,
+        K: Key + 'static,
+        F: Call<(I,), K> + 'static,
+    {
+        self.rank(key, order, None, emit, ctx)
+    }
+
+    /// Emits the `k` first elements of each window of the stream as sorted by `key`. Only `k`
+    /// elements are retained per window.
+    pub fn top_k<K, F>(
+        self,
+        key: F,
+        order: Order,
+        k: usize,
+        emit: Emit<I>,
+        ctx: Context,
+    ) -> clm::Pullable<Vec<I>>
+    where
+        I: 'static,
+        K: Key + 'static,
+        F: Call<(I,), K> + 'static,
+    {
+        self.rank(key, order, Some(k), emit, ctx)
+    }
+
+    fn rank<K, F>(
+        mut self,
+        key: F,
+        order: Order,
+        limit: std::option::Option<usize>,
+        emit: Emit<I>,
+        ctx: Context,
+    ) -> clm::Pullable<Vec<I>>
+    where
+        I: 'static,
+        K: Key + 'static,
+        F: Call<(I,), K> + 'static,
+    {
+        let (o0, o1) = clm::channel(ctx);
+        ctx.spawn(move |ctx| async move {
+            let mut ranking = Ranking::new(order, limit);
+            let mut windows = Windows::new(emit);
+            loop {
+                let x = match self.pull(ctx).await {
+                    Control::Continue(x) => x,
+                    Control::Finished => break,
+                    Control::Failed(error) => return o0.fail(error).await,
+                };
+                if windows.closes_before(&x, ctx) {
+                    o0.push(Vec::from_iter(ranking.drain(), ctx), ctx).await?;
+                }
+                ranking.insert(key.call((x.clone(),), ctx), x);
+                if windows.is_incremental() {
+                    o0.push(Vec::from_iter(ranking.sorted(), ctx), ctx).await?;
+                } else if windows.closes_after() {
+                    o0.push(Vec::from_iter(ranking.drain(), ctx), ctx).await?;
+                }
+            }
+            if !windows.is_incremental() && !ranking.is_empty() {
+                o0.push(Vec::from_iter(ranking.drain(), ctx), ctx).await?;
+            }
+            Control::Finished
+        });
+        o1
+    }
+}
@@ -0,0 +1,109 @@
+//! Policies which decide when a windowed operator emits its results.
+
+use crate::prelude::*;
+
+use std::sync::Arc;
+
+/// When a windowed operator should emit results.
+pub enum Emit<I> {
+    /// Emit an updated result for every input element.
+    Incremental,
+    /// Emit and reset after every `n` input elements.
+    Count(usize),
+    /// Emit and reset at the end of every tumbling event-time window, see [`Emit::tumbling`].
+    Tumbling(Tumbling<I>),
+    /// Emit once the input is exhausted.
+    Final,
+}
+
+/// A tumbling event-time window. Elements are assumed to arrive in order.
+pub struct Tumbling<I> {
+    length: Duration,
+    time: Arc<dyn Fn(I, Context) -> DateTime + Send + Sync>,
+}
+
+impl<I: 'static> Emit<I> {
+    /// Emit and reset at the end of every tumbling window of `length`, where the timestamp of
+    /// each element is given by `time`. Panics if `length` is not positive.
+    pub fn tumbling<F: Call<(I,), DateTime> + 'static>(
+        length: Duration,
+        time: F,
+        _: Context,
+    ) -> Self {
+        assert!(
+            length.0.is_positive(),
+            "The length of a tumbling window must be positive, found {}",
+            length.0
+        );
+        Emit::Tumbling(Tumbling {
+            length,
+            time: Arc::new(move |input, ctx| time.call((input,), ctx)),
+        })
+    }
+}
+
+impl<I> Clone for Emit<I> {
+    fn clone(&self) -> Self {
+        match self {
+            Emit::Incremental => Emit::Incremental,
+            Emit::Count(n) => Emit::Count(*n),
+            Emit::Tumbling(w) => Emit::Tumbling(Tumbling {
+                length: w.length,
+                time: w.time.clone(),
+            }),
+            Emit::Final => Emit::Final,
+        }
+    }
+}
+
+/// Tracks the window which is currently open.
+pub(crate) struct Windows<I> {
+    emit: Emit<I>,
+    count: usize,
+    current: std::option::Option<i128>,
+}
+
+impl<I: Clone> Windows<I> {
+    pub(crate) fn new(emit: Emit<I>) -> Self {
+        Self {
+            emit,
+            count: 0,
+            current: None,
+        }
+    }
+
+    pub(crate) fn is_incremental(&self) -> bool {
+        matches!(self.emit, Emit::Incremental)
+    }
+
+    /// Returns `true` if the open window must be closed before `input` is added to it.
+    pub(crate) fn closes_before(&mut self, input: &I, ctx: Context) -> bool {
+        if let Emit::Tumbling(w) = &self.emit {
+            let index = window_index((w.time)(input.clone(), ctx), w.length);
+            let closes = matches!(self.current, Some(current) if current < index);
+            self.current = Some(index);
+            closes
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the open window must be closed after an input has been added to it.
+    pub(crate) fn closes_after(&mut self) -> bool {
+        if let Emit::Count(n) = self.emit {
+            self.count += 1;
+            if self.count >= n {
+                self.count = 0;
+                return true;
+            }
+        }
+        false
+    }
+}
+
+fn window_index(time: DateTime, length: Duration) -> i128 {
+    (time - DateTime::EPOCH)
+        .0
+        .whole_nanoseconds()
+        .div_euclid(length.0.whole_nanoseconds())
+}
@@ -0,0 +1,139 @@
+//! Tasks which write records to files.
+//!
+//! Records are pulled in their sendable form and serialized. The file is flushed and closed
+//! once the input channel is closed. Files whose path ends with `.gz` are compressed.
+//! Writing blocks, so every file is written by a thread of its own. Every sink returns a
+//! channel which is closed once the file has been closed, and failed if writing failed.
+
+use crate::control::Error;
+use crate::data::channels::local::multicast as clm;
+use crate::prelude::*;
+use crate::task::blocking;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+
+enum Output {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl Output {
+    fn create(path: &str) -> std::result::Result<Self, Error> {
+        let file = File::create(path)
+            .map_err(|e| Error::new(format!("Failed to create {}: {}", path, e)))?;
+        if path.ends_with(".gz") {
+            Ok(Output::Gzip(GzEncoder::new(
+                BufWriter::new(file),
+                Compression::default(),
+            )))
+        } else {
+            Ok(Output::Plain(BufWriter::new(file)))
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Output::Plain(mut w) => w.flush(),
+            Output::Gzip(w) => w.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Plain(w) => w.write(buf),
+            Output::Gzip(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Plain(w) => w.flush(),
+            Output::Gzip(w) => w.flush(),
+        }
+    }
+}
+
+/// Writes records as comma-separated values to `path`. If `header` is `true`, then a row
+/// with the field names is written before the first record.
+pub fn csv<T: Sharable + 'static>(
+    input: clm::Pullable<T>,
+    path: &str,
+    header: bool,
+    ctx: Context,
+) -> clm::Pullable<unit> {
+    let path = path.to_string();
+    blocking::sink(
+        input,
+        move |records| {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(header)
+                .from_writer(Output::create(&path)?);
+            for record in records {
+                writer.serialize(record).map_err(|e| {
+                    Error::new(format!("Failed to write record to {}: {}", path, e))
+                })?;
+            }
+            writer
+                .into_inner()
+                .map_err(|e| Error::new(format!("Failed to close {}: {}", path, e.error())))?
+                .finish()
+                .map_err(|e| Error::new(format!("Failed to close {}: {}", path, e)))
+        },
+        ctx,
+    )
+}
+
+/// Writes records as newline-delimited JSON to `path`.
+pub fn json<T: Sharable + 'static>(
+    input: clm::Pullable<T>,
+    path: &str,
+    ctx: Context,
+) -> clm::Pullable<unit> {
+    let path = path.to_string();
+    blocking::sink(
+        input,
+        move |records| {
+            let mut writer = Output::create(&path)?;
+            for record in records {
+                serde_json::to_writer(&mut writer, &record)
+                    .map_err(io::Error::from)
+                    .and_then(|_| writer.write_all(b"\n"))
+                    .map_err(|e| {
+                        Error::new(format!("Failed to write record to {}: {}", path, e))
+                    })?;
+            }
+            writer
+                .finish()
+                .map_err(|e| Error::new(format!("Failed to close {}: {}", path, e)))
+        },
+        ctx,
+    )
+}
+
+/// Writes raw bytes to `path`, one element after another.
+pub fn bytes(input: clm::Pullable<Bytes>, path: &str, ctx: Context) -> clm::Pullable<unit> {
+    let path = path.to_string();
+    blocking::sink(
+        input,
+        move |chunks| {
+            let mut writer = Output::create(&path)?;
+            for data in chunks {
+                writer
+                    .write_all(&data.0)
+                    .map_err(|e| Error::new(format!("Failed to write bytes to {}: {}", path, e)))?;
+            }
+            writer
+                .finish()
+                .map_err(|e| Error::new(format!("Failed to close {}: {}", path, e)))
+        },
+        ctx,
+    )
+}
@@ -0,0 +1,59 @@
+//! Tasks which write frames to TCP sockets.
+//!
+//! Every connection is served by a thread of its own, see [`crate::source::tcp`].
+
+use crate::control::Error;
+use crate::data::channels::local::multicast as clm;
+use crate::prelude::*;
+use crate::source::tcp::failed;
+use crate::source::tcp::Framing;
+use crate::source::tcp::Reconnect;
+use crate::task::blocking;
+
+use std::io::BufWriter;
+use std::io::Write;
+use std::net::TcpStream;
+
+/// Connects to `addr` and writes every record as a frame. If writing a frame fails, then it
+/// is resent on a new connection at most `reconnect.attempts` times. Returns a channel which
+/// is closed once the input channel is closed and all frames have been written, and failed
+/// if a frame could not be written.
+pub fn json<T: Sharable + 'static>(
+    input: clm::Pullable<T>,
+    addr: &str,
+    framing: Framing,
+    reconnect: Reconnect,
+    ctx: Context,
+) -> clm::Pullable<unit> {
+    let addr = addr.to_string();
+    blocking::sink(
+        input,
+        move |records| {
+            let mut writer: std::option::Option<BufWriter<TcpStream>> = None;
+            for data in records {
+                let frame = serde_json::to_vec(&data)
+                    .map_err(|e| Error::new(format!("Failed to write record to socket: {}", e)))?;
+                let mut attempt = 0;
+                loop {
+                    let w = match &mut writer {
+                        Some(w) => w,
+                        None => match reconnect.connect(&addr) {
+                            Ok(stream) => writer.insert(BufWriter::new(stream)),
+                            Err(e) => return Err(failed(&addr, e)),
+                        },
+                    };
+                    match framing.write(w, &frame).and_then(|_| w.flush()) {
+                        Ok(()) => break,
+                        Err(e) if attempt >= reconnect.attempts => return Err(failed(&addr, e)),
+                        Err(_) => {
+                            attempt += 1;
+                            writer = None;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        },
+        ctx,
+    )
+}
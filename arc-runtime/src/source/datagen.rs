@@ -0,0 +1,161 @@
+//! A task which generates synthetic events, for benchmarks and soak tests.
+//!
+//! Events are sampled from a distribution with a seeded generator, so the same seed always
+//! produces the same events. Every event is stamped with an event time which advances by
+//! `1 / rate` seconds per event, and events are released at `rate` events per second of
+//! wall-clock time.
+
+use crate::data::channels::local::multicast as clm;
+use crate::prelude::*;
+
+use rand::distributions::Distribution;
+use rand::distributions::Uniform;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_distr::Normal;
+use rand_distr::Zipf;
+
+use std::marker::PhantomData;
+use std::time::Instant;
+
+/// A distribution of numeric keys or values.
+#[derive(Clone, Copy, Debug)]
+pub enum Dist {
+    Uniform(Uniform<f64>),
+    Zipf(Zipf<f64>),
+    Gaussian(Normal<f64>),
+}
+
+impl Dist {
+    /// Uniformly distributed over `[low, high)`.
+    pub fn uniform(low: f64, high: f64) -> Self {
+        Dist::Uniform(Uniform::new(low, high))
+    }
+
+    /// Zipf-distributed over the integers `[1, n]` with exponent `s`.
+    pub fn zipf(n: u64, s: f64) -> Self {
+        Dist::Zipf(Zipf::new(n, s).expect("Invalid zipf distribution"))
+    }
+
+    /// Normally distributed around `mean` with standard deviation `std_dev`.
+    pub fn gaussian(mean: f64, std_dev: f64) -> Self {
+        Dist::Gaussian(Normal::new(mean, std_dev).expect("Invalid gaussian distribution"))
+    }
+
+    fn sample_f64<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            Dist::Uniform(d) => d.sample(rng),
+            Dist::Zipf(d) => d.sample(rng),
+            Dist::Gaussian(d) => d.sample(rng),
+        }
+    }
+}
+
+/// Integers are sampled by rounding down, so that `Dist::uniform(low, high)` covers every
+/// integer in `[low, high)` equally often. Samples outside the range of the integer type
+/// saturate at its bounds.
+macro_rules! impl_dist {
+    (floor: $($int:ty),*; $($float:ty),*) => {
+        $(
+            impl Distribution<$int> for Dist {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $int {
+                    self.sample_f64(rng).floor() as $int
+                }
+            }
+        )*
+        $(
+            impl Distribution<$float> for Dist {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $float {
+                    self.sample_f64(rng) as $float
+                }
+            }
+        )*
+    };
+}
+
+impl_dist!(floor: i8, i16, i32, i64, i128, u8, u16, u32, u64, u128; f32, f64);
+
+/// Samples keys and values independently from two distributions.
+#[derive(Clone, Copy, Debug)]
+pub struct KeyValue<K, V>(pub K, pub V);
+
+impl<A, B, K: Distribution<A>, V: Distribution<B>> Distribution<(A, B)> for KeyValue<K, V> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (A, B) {
+        (self.0.sample(rng), self.1.sample(rng))
+    }
+}
+
+/// An endless iterator of timestamped samples from `dist`.
+pub struct DataGen<T, D> {
+    dist: D,
+    rng: StdRng,
+    rate: f64,
+    time: DateTime,
+    interval: Duration,
+    marker: PhantomData<T>,
+}
+
+impl<T, D: Distribution<T>> DataGen<T, D> {
+    /// Creates a generator of `rate` events per second, starting at the unix epoch. An infinite
+    /// `rate` stamps every event with the same time and generates events as fast as possible.
+    pub fn new(dist: D, rate: f64, seed: u64) -> Self {
+        assert!(rate > 0.0, "Rate must be positive");
+        Self {
+            dist,
+            rng: StdRng::seed_from_u64(seed),
+            rate,
+            time: DateTime::EPOCH,
+            interval: Duration(time::Duration::seconds_f64(1.0 / rate)),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, D: Distribution<T>> Iterator for DataGen<T, D> {
+    type Item = (DateTime, T);
+
+    fn next(&mut self) -> std::option::Option<Self::Item> {
+        let time = self.time;
+        self.time += self.interval;
+        Some((time, self.dist.sample(&mut self.rng)))
+    }
+}
+
+/// Generates `count` events, or endlessly if `count` is `None`, and builds a record of each
+/// with `output`. Sampling and pacing happen on a thread of their own.
+pub fn datagen<T, D, F, O>(
+    gen: DataGen<T, D>,
+    count: std::option::Option<usize>,
+    output: F,
+    ctx: Context,
+) -> clm::Pullable<O>
+where
+    T: Send + 'static,
+    D: Distribution<T> + Send + 'static,
+    F: Call<(DateTime, T), O> + 'static,
+    O: Sharable + 'static,
+{
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1024);
+    std::thread::spawn(move || {
+        let start = Instant::now();
+        let rate = gen.rate;
+        for (i, event) in gen.take(count.unwrap_or(usize::MAX)).enumerate() {
+            let due = start + std::time::Duration::from_secs_f64(i as f64 / rate);
+            if let Some(wait) = due.checked_duration_since(Instant::now()) {
+                std::thread::sleep(wait);
+            }
+            if tx.blocking_send(event).is_err() {
+                break;
+            }
+        }
+    });
+    let (o0, o1) = clm::channel(ctx);
+    ctx.spawn(move |ctx| async move {
+        while let Some(event) = rx.recv().await {
+            o0.push(output.call(event, ctx), ctx).await?;
+        }
+        Control::Finished
+    });
+    o1
+}
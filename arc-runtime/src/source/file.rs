@@ -0,0 +1,87 @@
+//! Tasks which read records from files.
+//!
+//! Records are deserialized into the sendable form of their type and pushed into a channel,
+//! which is closed when the end of the file is reached. Files whose path ends with `.gz` are
+//! decompressed while being read. Reading blocks, so every file is read by a thread of its
+//! own. The channel is failed if the file cannot be read, or if a record is malformed.
+
+use crate::control::Error;
+use crate::data::bytes;
+use crate::data::channels::local::multicast as clm;
+use crate::prelude::*;
+use crate::task::blocking;
+
+use flate2::read::MultiGzDecoder;
+
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+
+fn open(path: &str) -> std::result::Result<Box<dyn Read + Send>, Error> {
+    let file =
+        File::open(path).map_err(|e| Error::new(format!("Failed to open {}: {}", path, e)))?;
+    if path.ends_with(".gz") {
+        Ok(Box::new(MultiGzDecoder::new(BufReader::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Reads comma-separated records from `path`. If `header` is `true`, then columns are matched
+/// with fields by the names in the first row, otherwise they are matched by position.
+pub fn csv<T: Sharable + 'static>(path: &str, header: bool, ctx: Context) -> clm::Pullable<T> {
+    let path = path.to_string();
+    blocking::source(
+        move |tx| {
+            let reader = csv::ReaderBuilder::new()
+                .has_headers(header)
+                .from_reader(open(&path)?);
+            for record in reader.into_deserialize::<T::T>() {
+                let record = record.map_err(|e| {
+                    Error::new(format!("Failed to read record from {}: {}", path, e))
+                })?;
+                if !tx.emit(record) {
+                    break;
+                }
+            }
+            Ok(())
+        },
+        ctx,
+    )
+}
+
+/// Reads newline-delimited JSON records from `path`.
+pub fn json<T: Sharable + 'static>(path: &str, ctx: Context) -> clm::Pullable<T> {
+    let path = path.to_string();
+    blocking::source(
+        move |tx| {
+            let reader = serde_json::Deserializer::from_reader(open(&path)?);
+            for record in reader.into_iter::<T::T>() {
+                let record = record.map_err(|e| {
+                    Error::new(format!("Failed to read record from {}: {}", path, e))
+                })?;
+                if !tx.emit(record) {
+                    break;
+                }
+            }
+            Ok(())
+        },
+        ctx,
+    )
+}
+
+/// Reads the contents of `path` as a single element of raw bytes.
+pub fn bytes(path: &str, ctx: Context) -> clm::Pullable<Bytes> {
+    let path = path.to_string();
+    blocking::source(
+        move |tx| {
+            let mut data = std::vec::Vec::new();
+            open(&path)?
+                .read_to_end(&mut data)
+                .map_err(|e| Error::new(format!("Failed to read {}: {}", path, e)))?;
+            tx.emit(bytes::sendable::Bytes(data.into_boxed_slice()));
+            Ok(())
+        },
+        ctx,
+    )
+}
@@ -0,0 +1,3 @@
+pub mod datagen;
+pub mod file;
+pub mod tcp;
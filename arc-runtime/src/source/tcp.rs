@@ -0,0 +1,213 @@
+//! Tasks which read frames from TCP sockets.
+//!
+//! Sockets are blocking, so every connection is served by a thread of its own, see
+//! [`crate::task::blocking`]. Each frame holds either raw bytes, or one record in its
+//! JSON-serialized sendable form.
+
+use crate::control::Error;
+use crate::data::bytes;
+use crate::data::channels::local::multicast as clm;
+use crate::prelude::*;
+use crate::task::blocking;
+
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::net::TcpStream;
+
+/// How frames are delimited in a byte stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Framing {
+    /// Every frame is terminated by a newline.
+    Lines,
+    /// Every frame is preceded by its length as a big-endian `u32`. Frames which are longer
+    /// than `max_len` bytes are rejected instead of being buffered.
+    LengthPrefixed { max_len: usize },
+}
+
+impl Framing {
+    /// Reads the next frame, or returns `None` if the stream has ended between two frames.
+    pub fn read(self, r: &mut impl BufRead) -> io::Result<std::option::Option<std::vec::Vec<u8>>> {
+        match self {
+            Framing::Lines => {
+                let mut frame = std::vec::Vec::new();
+                if r.read_until(b'\n', &mut frame)? == 0 {
+                    return Ok(None);
+                }
+                if frame.last() == Some(&b'\n') {
+                    frame.pop();
+                    if frame.last() == Some(&b'\r') {
+                        frame.pop();
+                    }
+                }
+                Ok(Some(frame))
+            }
+            Framing::LengthPrefixed { max_len } => {
+                if r.fill_buf()?.is_empty() {
+                    return Ok(None);
+                }
+                let mut len = [0; 4];
+                r.read_exact(&mut len)?;
+                let len = u32::from_be_bytes(len) as usize;
+                if len > max_len {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Frame of {} bytes exceeds the maximum of {}", len, max_len),
+                    ));
+                }
+                let mut frame = vec![0; len];
+                r.read_exact(&mut frame)?;
+                Ok(Some(frame))
+            }
+        }
+    }
+
+    pub fn write(self, w: &mut impl Write, frame: &[u8]) -> io::Result<()> {
+        match self {
+            Framing::Lines => {
+                w.write_all(frame)?;
+                w.write_all(b"\n")
+            }
+            Framing::LengthPrefixed { max_len } => {
+                let len = u32::try_from(frame.len())
+                    .ok()
+                    .filter(|len| *len as usize <= max_len)
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            ErrorKind::InvalidInput,
+                            format!(
+                                "Frame of {} bytes exceeds the maximum of {}",
+                                frame.len(),
+                                max_len
+                            ),
+                        )
+                    })?;
+                w.write_all(&len.to_be_bytes())?;
+                w.write_all(frame)
+            }
+        }
+    }
+}
+
+/// How many times, and how often, to retry connecting when a connection cannot be
+/// established or fails with an error.
+#[derive(Clone, Copy, Debug)]
+pub struct Reconnect {
+    pub attempts: usize,
+    pub delay: std::time::Duration,
+}
+
+impl Reconnect {
+    pub const NEVER: Reconnect = Reconnect {
+        attempts: 0,
+        delay: std::time::Duration::ZERO,
+    };
+
+    pub(crate) fn connect(self, addr: &str) -> io::Result<TcpStream> {
+        let mut attempt = 0;
+        loop {
+            match TcpStream::connect(addr) {
+                Ok(stream) => return Ok(stream),
+                Err(e) if attempt >= self.attempts => return Err(e),
+                Err(_) => {
+                    attempt += 1;
+                    std::thread::sleep(self.delay);
+                }
+            }
+        }
+    }
+}
+
+/// The frames received from `addr`. Ends when the peer closes the connection. Fails when the
+/// connection fails and cannot be re-established, or when a frame is malformed.
+struct Frames {
+    addr: std::string::String,
+    framing: Framing,
+    reconnect: Reconnect,
+    reader: std::option::Option<BufReader<TcpStream>>,
+}
+
+impl Iterator for Frames {
+    type Item = std::result::Result<std::vec::Vec<u8>, Error>;
+
+    fn next(&mut self) -> std::option::Option<Self::Item> {
+        loop {
+            let reader = match &mut self.reader {
+                Some(reader) => reader,
+                None => match self.reconnect.connect(&self.addr) {
+                    Ok(stream) => self.reader.insert(BufReader::new(stream)),
+                    Err(e) => return Some(Err(failed(&self.addr, e))),
+                },
+            };
+            match self.framing.read(reader) {
+                Ok(frame) => return frame.map(Ok),
+                Err(e) if e.kind() == ErrorKind::InvalidData => {
+                    return Some(Err(failed(&self.addr, e)))
+                }
+                Err(_) => self.reader = None,
+            }
+        }
+    }
+}
+
+pub(crate) fn failed(addr: &str, e: impl std::fmt::Display) -> Error {
+    Error::new(format!("Connection to {} failed: {}", addr, e))
+}
+
+/// Connects to `addr` and reads every frame as raw bytes. The channel is closed when the
+/// connection is closed by the peer.
+pub fn bytes(
+    addr: &str,
+    framing: Framing,
+    reconnect: Reconnect,
+    ctx: Context,
+) -> clm::Pullable<Bytes> {
+    let frames = Frames {
+        addr: addr.to_string(),
+        framing,
+        reconnect,
+        reader: None,
+    };
+    blocking::source(
+        move |tx| {
+            for frame in frames {
+                if !tx.emit(bytes::sendable::Bytes(frame?.into_boxed_slice())) {
+                    break;
+                }
+            }
+            Ok(())
+        },
+        ctx,
+    )
+}
+
+/// Connects to `addr` and reads one record from every frame. The channel is closed when the
+/// connection is closed by the peer, and failed if a frame does not hold a record.
+pub fn json<T: Sharable + 'static>(
+    addr: &str,
+    framing: Framing,
+    reconnect: Reconnect,
+    ctx: Context,
+) -> clm::Pullable<T> {
+    let frames = Frames {
+        addr: addr.to_string(),
+        framing,
+        reconnect,
+        reader: None,
+    };
+    blocking::source(
+        move |tx| {
+            for frame in frames {
+                let data = serde_json::from_slice::<T::T>(&frame?)
+                    .map_err(|e| Error::new(format!("Failed to read record from socket: {}", e)))?;
+                if !tx.emit(data) {
+                    break;
+                }
+            }
+            Ok(())
+        },
+        ctx,
+    )
+}
@@ -0,0 +1,64 @@
+use futures::future::LocalBoxFuture;
+use futures::FutureExt;
+use kompact::prelude::*;
+
+use crate::prelude::instantiate_immix;
+use crate::prelude::Context;
+use crate::prelude::Control;
+use crate::prelude::ImmixOptions;
+use crate::prelude::TaskMessage;
+
+use std::future::Future;
+
+type Body = Box<dyn FnOnce(Context) -> LocalBoxFuture<'static, Control<()>> + Send>;
+
+/// A task whose behaviour is given by a closure instead of a `#[rewrite]`-generated
+/// component. This is what builtin operators, sources and sinks run on. If the closure fails,
+/// then its error is logged.
+#[derive(ComponentDefinition)]
+pub struct AnonymousTask {
+    ctx: ComponentContext<Self>,
+    body: std::option::Option<Body>,
+}
+
+impl AnonymousTask {
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: FnOnce(Context) -> Fut + Send + 'static,
+        Fut: Future<Output = Control<()>> + 'static,
+    {
+        Self {
+            ctx: ComponentContext::uninitialised(),
+            body: Some(Box::new(move |ctx| f(ctx).boxed_local())),
+        }
+    }
+}
+
+impl Actor for AnonymousTask {
+    type Message = TaskMessage;
+
+    fn receive_local(&mut self, _: Self::Message) -> Handled {
+        Handled::Ok
+    }
+
+    fn receive_network(&mut self, _: NetMessage) -> Handled {
+        unreachable!()
+    }
+}
+
+impl ComponentLifecycle for AnonymousTask {
+    fn on_start(&mut self) -> Handled {
+        let body = self.body.take().expect("Task was started twice");
+        self.spawn_local(move |async_self| async move {
+            let component = async_self.ctx().component();
+            let mutator = instantiate_immix(ImmixOptions::default());
+            let ctx = Context::new(component, mutator);
+            if let Control::Failed(e) = body(ctx).await {
+                error!(async_self.log(), "Task failed: {}", e);
+            }
+            ctx.destroy();
+            Handled::DieNow
+        });
+        Handled::Ok
+    }
+}
@@ -0,0 +1,103 @@
+//! Tasks whose work blocks, such as file and socket I/O.
+//!
+//! The blocking work runs on a thread of its own, which exchanges data with an anonymous task
+//! through a bounded queue. The task ends when the thread does, and fails with the error of
+//! the thread if it returns one.
+
+use crate::control::Error;
+use crate::data::channels::local::multicast as clm;
+use crate::prelude::*;
+
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+/// The capacity of the queue between a thread and its task.
+const CAPACITY: usize = 100;
+
+/// The end of the queue through which a thread sends data to its task.
+pub struct Emitter<T>(mpsc::Sender<std::result::Result<T, Error>>);
+
+impl<T> Emitter<T> {
+    /// Sends `data` to the task. Returns `false` if the task has ended, in which case the
+    /// thread should stop.
+    pub fn emit(&self, data: T) -> bool {
+        self.0.blocking_send(Ok(data)).is_ok()
+    }
+}
+
+/// The end of the queue through which a thread receives data from its task. Iteration ends
+/// when the input of the task does.
+pub struct Inputs<T>(mpsc::Receiver<T>);
+
+impl<T> Iterator for Inputs<T> {
+    type Item = T;
+
+    fn next(&mut self) -> std::option::Option<T> {
+        self.0.blocking_recv()
+    }
+}
+
+/// Runs `read` on a thread of its own, and returns a channel with the data it emits. The
+/// channel is failed if `read` returns an error.
+pub fn source<T, F>(read: F, ctx: Context) -> clm::Pullable<T>
+where
+    T: Sharable + 'static,
+    F: FnOnce(&Emitter<T::T>) -> std::result::Result<(), Error> + Send + 'static,
+{
+    let (tx, mut rx) = mpsc::channel(CAPACITY);
+    std::thread::spawn(move || {
+        let tx = Emitter(tx);
+        if let Err(e) = read(&tx) {
+            let _ = tx.0.blocking_send(Err(e));
+        }
+    });
+    let (o0, o1) = clm::channel(ctx);
+    ctx.spawn(move |_| async move {
+        while let Some(data) = rx.recv().await {
+            match data {
+                Ok(data) => o0.push_sendable(data).await?,
+                Err(e) => return o0.fail(e).await,
+            }
+        }
+        Control::Finished
+    });
+    o1
+}
+
+/// Runs `write` on a thread of its own with the data of `input`. Returns a channel which is
+/// closed once `write` has returned, and failed if either `write` or `input` failed.
+pub fn sink<T, F>(mut input: clm::Pullable<T>, write: F, ctx: Context) -> clm::Pullable<unit>
+where
+    T: Sharable + 'static,
+    F: FnOnce(Inputs<T::T>) -> std::result::Result<(), Error> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(CAPACITY);
+    let (done_tx, done_rx) = oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = done_tx.send(write(Inputs(rx)));
+    });
+    let (o0, o1) = clm::channel(ctx);
+    ctx.spawn(move |_| async move {
+        let failed = loop {
+            match input.pull_sendable().await {
+                Control::Continue(data) => {
+                    if tx.send(data).await.is_err() {
+                        break None;
+                    }
+                }
+                Control::Finished => break None,
+                Control::Failed(e) => break Some(e),
+            }
+        };
+        drop(tx);
+        match done_rx.await {
+            Ok(Ok(())) => match failed {
+                Some(e) => o0.fail(e).await,
+                None => Control::Finished,
+            },
+            Ok(Err(e)) => o0.fail(e).await,
+            Err(_) => o0.fail(Error::new("The thread of a sink panicked")).await,
+        }
+    });
+    o1
+}
@@ -1 +1,3 @@
+pub mod anonymous;
+pub mod blocking;
 pub mod message;
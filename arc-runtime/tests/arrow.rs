@@ -0,0 +1,96 @@
+#![cfg(feature = "arrow")]
+#![allow(unused)]
+
+use arc_runtime::data::arrow;
+use arc_runtime::data::arrow::Array;
+use arc_runtime::prelude::*;
+
+#[rewrite(arrow)]
+pub struct Reading {
+    pub sensor: String,
+    pub values: Vec<f64>,
+    pub location: Location,
+    pub valid: bool,
+}
+
+#[rewrite(arrow)]
+pub struct Location {
+    pub x: i32,
+    pub y: i32,
+}
+
+fn readings(ctx: Context) -> Vec<Reading> {
+    let a: Reading = ConcreteReading {
+        sensor: String::from_str("a", ctx),
+        values: Vec::from_iter([1.0, 2.0], ctx),
+        location: ConcreteLocation { x: 1, y: 2 }.alloc(ctx),
+        valid: true,
+    }
+    .alloc(ctx);
+    let b: Reading = ConcreteReading {
+        sensor: String::from_str("b", ctx),
+        values: Vec::new(ctx),
+        location: ConcreteLocation { x: 3, y: 4 }.alloc(ctx),
+        valid: false,
+    }
+    .alloc(ctx);
+    Vec::from_iter([a, b], ctx)
+}
+
+fn check(rs: Vec<Reading>, ctx: Context) {
+    assert_eq!(Vec::len(rs.clone(), ctx), 2);
+    let a = Option::unwrap(Vec::get(rs.clone(), 0, ctx), ctx);
+    let b = Option::unwrap(Vec::get(rs, 1, ctx), ctx);
+    assert_eq!(a.sensor.as_str(), "a");
+    assert_eq!(a.values.as_slice(ctx), &[1.0, 2.0]);
+    assert_eq!(a.location.y, 2);
+    assert!(a.valid);
+    assert_eq!(b.sensor.as_str(), "b");
+    assert!(Vec::is_empty(b.values.clone(), ctx));
+    assert_eq!(b.location.x, 3);
+    assert!(!b.valid);
+}
+
+fn round_trip(ctx: Context) {
+    let schema = arrow::schema::<Reading>();
+    assert_eq!(schema.fields.len(), 4);
+    assert_eq!(schema.fields[0].data_type(), &arrow::DataType::Utf8);
+    let chunk = arrow::to_chunk(readings(ctx), ctx);
+    assert_eq!(chunk.len(), 2);
+    check(arrow::from_chunk(&chunk, ctx).unwrap(), ctx);
+}
+
+fn sliced(ctx: Context) {
+    let lists = [
+        Vec::from_iter([1.0], ctx),
+        Vec::from_iter([2.0, 3.0], ctx),
+        Vec::from_iter([4.0], ctx),
+    ];
+    let array = <Vec<f64> as Arrow>::to_array(&lists).slice(1, 2);
+    let lists = <Vec<f64> as Arrow>::from_array(&*array, ctx).unwrap();
+    assert_eq!(lists.len(), 2);
+    assert_eq!(lists[0].as_slice(ctx), &[2.0, 3.0]);
+    assert_eq!(lists[1].as_slice(ctx), &[4.0]);
+}
+
+fn nulls(ctx: Context) {
+    let array = arrow::PrimitiveArray::<i32>::from(&[Some(1), None]);
+    assert!(<i32 as Arrow>::from_array(&array, ctx).is_err());
+    let path = concat!(env!("CARGO_TARGET_TMPDIR"), "/missing.arrow");
+    assert!(arrow::read_ipc::<Reading>(path, ctx).is_err());
+}
+
+fn ipc(ctx: Context) {
+    let path = concat!(env!("CARGO_TARGET_TMPDIR"), "/readings.arrow");
+    arrow::write_ipc(path, readings(ctx), ctx);
+    check(arrow::read_ipc(path, ctx).unwrap(), ctx);
+}
+
+#[rewrite(main)]
+#[test]
+fn conversion() {
+    round_trip();
+    sliced();
+    nulls();
+    ipc();
+}
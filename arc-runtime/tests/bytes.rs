@@ -0,0 +1,113 @@
+#![allow(unused)]
+
+use arc_runtime::data::channels::local::multicast::Pullable;
+use arc_runtime::prelude::*;
+use arc_runtime::sink;
+use arc_runtime::source;
+use arc_runtime::source::tcp::Framing;
+use arc_runtime::source::tcp::Reconnect;
+
+use futures::TryStreamExt;
+
+use std::io::Read;
+use std::net::TcpListener;
+
+#[rewrite(main)]
+#[test]
+fn bytes() {
+    let a: Bytes = Bytes::from_string(String::from_str("hello"));
+    let b: Bytes = Bytes::from_vec(vector![0u8, 255u8,]);
+    assert_eq!(Bytes::len(a), 5);
+    assert_eq!(Option::unwrap_or(Bytes::get(b, 1), 0), 255);
+    assert!(Option::is_none(Bytes::get(b, 2)));
+    let c: Bytes = Bytes::concat(a, b);
+    assert!(String::eq(
+        Bytes::to_hex(c),
+        String::from_str("68656c6c6f00ff")
+    ));
+    let d: Bytes = Option::unwrap_or(Bytes::from_hex(Bytes::to_hex(c)), Bytes::new());
+    assert!(Bytes::eq(c, d));
+    assert!(String::eq(
+        Bytes::to_base64(a),
+        String::from_str("aGVsbG8=")
+    ));
+    let e: Bytes = Option::unwrap_or(Bytes::from_base64(String::from_str("aGVsbG8=")), b);
+    assert!(Bytes::eq(a, e));
+    assert!(Option::is_none(Bytes::from_hex(String::from_str("xyz"))));
+    assert!(Option::is_none(Bytes::to_string(b)));
+    let f: Bytes = Option::unwrap(Bytes::slice(c, 1, 4));
+    assert!(Option::is_none(Bytes::slice(c, 4, 1)));
+    assert!(Option::is_none(Bytes::slice(c, 0, 8)));
+    assert!(String::eq(
+        Option::unwrap_or(Bytes::to_string(f), String::new()),
+        String::from_str("ell")
+    ));
+    let g: Bytes = c.into_sendable(ctx).into_sharable(ctx);
+    assert!(Bytes::eq(c, g));
+}
+
+#[test]
+fn serde() {
+    let b = serde_json::from_str::<<Bytes as DynSharable>::T>("[1,2,3]").unwrap();
+    assert_eq!(&*b.0, &[1, 2, 3]);
+    assert_eq!(serde_json::to_string(&b).unwrap(), "[1,2,3]");
+}
+
+fn path(name: &str) -> std::string::String {
+    format!("{}/{}", env!("CARGO_TARGET_TMPDIR"), name)
+}
+
+/// Returns the concatenation of the bytes which are read from `s`.
+fn collect(s: Pullable<Bytes>) -> std::vec::Vec<u8> {
+    block_on(s.try_collect::<std::vec::Vec<_>>())
+        .unwrap()
+        .iter()
+        .flat_map(|b| b.as_slice().to_vec())
+        .collect()
+}
+
+fn file_copy(ctx: Context) {
+    let data = (0..=255).collect::<std::vec::Vec<u8>>();
+    std::fs::write(path("blob.bin"), &data).unwrap();
+    let s: Pullable<Bytes> = source::file::bytes(&path("blob.bin"), ctx);
+    let mut done = sink::file::bytes(s, &path("blob.bin.gz"), ctx);
+    assert!(matches!(block_on(done.pull(ctx)), Control::Finished));
+    let mut copy = std::vec::Vec::new();
+    flate2::read::GzDecoder::new(std::fs::File::open(path("blob.bin.gz")).unwrap())
+        .read_to_end(&mut copy)
+        .unwrap();
+    assert_eq!(copy, data);
+    let s: Pullable<Bytes> = source::file::bytes(&path("blob.bin.gz"), ctx);
+    assert_eq!(collect(s), data);
+}
+
+fn tcp_frames(ctx: Context) {
+    let framing = Framing::LengthPrefixed { max_len: 16 };
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let writer = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        framing.write(&mut stream, &[0, 1, 2]).unwrap();
+        framing.write(&mut stream, b"").unwrap();
+        framing.write(&mut stream, &[255, b'\n']).unwrap();
+    });
+    let reconnect = Reconnect {
+        attempts: 50,
+        delay: std::time::Duration::from_millis(20),
+    };
+    let s: Pullable<Bytes> = source::tcp::bytes(&addr, framing, reconnect, ctx);
+    let frames = block_on(s.try_collect::<std::vec::Vec<_>>()).unwrap();
+    writer.join().unwrap();
+    let frames = frames
+        .iter()
+        .map(|b| b.as_slice().to_vec())
+        .collect::<std::vec::Vec<_>>();
+    assert_eq!(frames, vec![vec![0, 1, 2], vec![], vec![255, b'\n']]);
+}
+
+#[rewrite(main)]
+#[test]
+fn pipelines() {
+    file_copy();
+    tcp_frames();
+}
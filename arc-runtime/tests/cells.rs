@@ -0,0 +1,50 @@
+#![allow(unused)]
+
+#[allow(non_camel_case_types)]
+mod test_toplevel {
+
+    use arc_runtime::prelude::*;
+
+    declare_functions!(increment);
+
+    #[rewrite]
+    fn increment(a: i32) -> i32 {
+        a + 1
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn aliasing() {
+        let a: Cell<i32> = Cell::new(1);
+        let b: Cell<i32> = a;
+        Cell::set(a, 2);
+        assert_eq!(Cell::get(b), 2);
+        Cell::update(b, function!(increment));
+        assert_eq!(Cell::get(a), 3);
+        assert_eq!(Cell::replace(a, 5), 3);
+        assert_eq!(Cell::get(b), 5);
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn swap_and_compare() {
+        let a: Cell<String> = Cell::new(String::from_str("a"));
+        let b: Cell<String> = Cell::new(String::from_str("b"));
+        let c: Cell<String> = b;
+        Cell::swap(a, b);
+        assert!(String::eq(Cell::get(c), String::from_str("a")));
+        Cell::swap(a, a);
+        assert!(String::eq(Cell::get(a), String::from_str("b")));
+        assert!(!Cell::compare_and_set(
+            a,
+            String::from_str("x"),
+            String::from_str("y")
+        ));
+        assert!(Cell::compare_and_set(
+            a,
+            String::from_str("b"),
+            String::from_str("c")
+        ));
+        assert!(String::eq(Cell::get(a), String::from_str("c")));
+    }
+}
@@ -24,3 +24,118 @@ mod test_toplevel {
         let z: i32 = call!(f(1,));
     }
 }
+
+#[allow(non_camel_case_types)]
+#[cfg(test)]
+mod test_captured {
+
+    use arc_runtime::prelude::*;
+
+    declare_functions!(f; closures: add);
+
+    #[rewrite]
+    fn f(a: i32) -> i32 {
+        a + a
+    }
+
+    fn add(n: i32, (x,): (i32,), ctx: Context) -> i32 {
+        n + x
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let x: function!((i32) -> i32) = closure!(add, 1);
+        let y: i32 = call_indirect!(x(2));
+        assert_eq!(y, 3);
+        let z: function!((i32) -> i32) = x.into_sendable(ctx).into_sharable(ctx);
+        let w: i32 = call_indirect!(z(2));
+        assert_eq!(w, 3);
+        let v: Vec<i32> = vector![1, 2, 3,];
+        let u: Vec<i32> = Vec::map(v, x);
+        assert_eq!(Option::unwrap(Vec::get(u, 2)), 4);
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[cfg(test)]
+mod test_registry {
+
+    use arc_runtime::prelude::*;
+
+    declare_functions!(f, g; closures: h);
+
+    #[rewrite]
+    fn f(a: i32) -> i32 {
+        a + a
+    }
+
+    #[rewrite]
+    fn g(a: bool) -> bool {
+        !a
+    }
+
+    fn h(n: i32, (x,): (i32,), ctx: Context) -> i32 {
+        n * x
+    }
+
+    #[test]
+    fn resolve() {
+        assert_eq!(Tag::ALL.len(), 3);
+        assert_eq!(Tag::from_name("g"), Some(Tag::g));
+        assert!(Function::<(i32,), i32>::resolve("f").is_ok());
+        assert!(matches!(
+            Function::<(i32,), i32>::resolve("g"),
+            Err(ResolveError::SignatureMismatch { .. })
+        ));
+        assert!(matches!(
+            Function::<(i32,), i32>::resolve("h"),
+            Err(ResolveError::Closure("h"))
+        ));
+        assert!(matches!(
+            Function::<(i32,), i32>::resolve("k"),
+            Err(ResolveError::UnknownFunction(_))
+        ));
+    }
+
+    #[test]
+    fn versions() {
+        use arc_runtime::data::functions::signature;
+        assert_eq!(signature::<(i32,), i32>(), "fn<Tuple<i32>,i32>");
+        assert_eq!(Tag::f.path(), "closures::test_registry::f");
+
+        let f = Function::<(i32,), i32>::resolve("f").unwrap();
+        let json = serde_json::to_string(&f).unwrap();
+        let f: Function<(i32,), i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(f.tag.0, Tag::f);
+
+        let (tag, version): (Tag, u64) = serde_json::from_str(&json).unwrap();
+        let json = serde_json::to_string(&(tag, version + 1)).unwrap();
+        let e = serde_json::from_str::<Function<(i32,), i32>>(&json).unwrap_err();
+        assert!(e.to_string().contains("version"));
+
+        let json = serde_json::to_string(&(tag, version)).unwrap();
+        assert!(serde_json::from_str::<Function<(bool,), bool>>(&json).is_err());
+    }
+
+    #[test]
+    fn receive() {
+        use serde_json::json;
+        type F = SendableFunction<(i32,), i32>;
+        let f = FunctionTag::<(i32,), i32>::new(Tag::f).version();
+        let h = FunctionTag::<(i32,), i32>::new(Tag::h).version();
+        let env = json!({ "tag": "i32", "payload": [50] });
+
+        let ok = json!({ "tag": [Tag::f, f], "env": null });
+        assert!(serde_json::from_value::<F>(ok).is_ok());
+        let ok = json!({ "tag": [Tag::h, h], "env": env });
+        assert!(serde_json::from_value::<F>(ok).is_ok());
+
+        let e = serde_json::from_value::<F>(json!({ "tag": [Tag::f, f + 1], "env": null }));
+        assert!(e.unwrap_err().to_string().contains("version"));
+        let e = serde_json::from_value::<F>(json!({ "tag": [Tag::h, h], "env": null }));
+        assert!(e.unwrap_err().to_string().contains("closure"));
+        let e = serde_json::from_value::<F>(json!({ "tag": [Tag::f, f], "env": env }));
+        assert!(e.unwrap_err().to_string().contains("does not capture"));
+    }
+}
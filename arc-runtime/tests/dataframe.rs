@@ -0,0 +1,58 @@
+#![cfg(feature = "dataframes")]
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+fn words(ctx: Context) -> DataFrame {
+    let words: Series = Series::from_vec(
+        String::from_str("word", ctx),
+        Vec::from_iter([1, 2, 1, 3, 1, 2], ctx),
+        ctx,
+    );
+    let counts: Series = Series::from_vec(
+        String::from_str("count", ctx),
+        Vec::from_iter([1, 1, 1, 1, 1, 1], ctx),
+        ctx,
+    );
+    DataFrame::from_series(Vec::from_iter([words, counts], ctx), ctx)
+}
+
+#[rewrite(main)]
+#[test]
+fn query() {
+    let df: DataFrame = words();
+    let keys: Vec<String> = vector![String::from_str("word"),];
+    let wc: DataFrame =
+        DataFrame::group_by_agg(df, keys, String::from_str("count"), String::from_str("sum"));
+    let wc: DataFrame = DataFrame::sort(wc, String::from_str("word"), false);
+    assert_eq!(DataFrame::height(wc), 3);
+    let top: DataFrame = DataFrame::head(wc, 1);
+    let w: Vec<i32> = Option::unwrap(DataFrame::to_vec(top, String::from_str("word")));
+    assert_eq!(Option::unwrap(Vec::get(w, 0)), 1);
+    let col: Series = Option::unwrap(DataFrame::column(df, String::from_str("word")));
+    assert!(Option::is_none(DataFrame::column(
+        df,
+        String::from_str("size")
+    )));
+    let ones: Series = Series::from_vec(String::from_str("one"), vector![1, 1, 1, 1, 1, 1,]);
+    let mask: Series = Series::eq(col, ones);
+    assert_eq!(DataFrame::height(DataFrame::filter(df, mask)), 3);
+}
+
+#[rewrite(main)]
+#[test]
+fn conversion() {
+    let df: DataFrame = words();
+    let df: DataFrame = df.into_sendable(ctx).into_sharable(ctx);
+    assert_eq!(DataFrame::height(df), 6);
+    let s: Series = Option::unwrap(DataFrame::column(df, String::from_str("count")));
+    let s: Series = s.into_sendable(ctx).into_sharable(ctx);
+    assert_eq!(Series::sum::<i32>(s), 6);
+}
+
+#[rewrite(main)]
+#[test]
+fn missing_file() {
+    let df: Result<DataFrame, String> = DataFrame::read_csv(String::from_str("missing.csv"));
+    assert!(Result::is_err(df));
+}
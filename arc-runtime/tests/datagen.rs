@@ -0,0 +1,92 @@
+#![allow(unused)]
+
+use arc_runtime::data::channels::local::multicast::Pullable;
+use arc_runtime::prelude::*;
+use arc_runtime::source::datagen::datagen;
+use arc_runtime::source::datagen::DataGen;
+use arc_runtime::source::datagen::Dist;
+use arc_runtime::source::datagen::KeyValue;
+
+use rand::distributions::Standard;
+
+use futures::TryStreamExt;
+
+use std::time::Instant;
+
+declare_functions!(event);
+
+#[rewrite]
+pub struct Event {
+    pub time: DateTime,
+    pub value: i32,
+}
+
+#[rewrite]
+fn event(time: DateTime, value: i32) -> Event {
+    new!(Event {
+        time: time,
+        value: value
+    })
+}
+
+#[test]
+fn seeded() {
+    let a = DataGen::<(u32, f64), _>::new(
+        KeyValue(Dist::zipf(10, 1.2), Dist::gaussian(0.0, 1.0)),
+        100.0,
+        7,
+    );
+    let b = DataGen::<(u32, f64), _>::new(
+        KeyValue(Dist::zipf(10, 1.2), Dist::gaussian(0.0, 1.0)),
+        100.0,
+        7,
+    );
+    let a = a.take(100).collect::<std::vec::Vec<_>>();
+    let b = b.take(100).collect::<std::vec::Vec<_>>();
+    assert_eq!(a, b);
+    assert!(a.iter().all(|(_, (k, _))| (1..=10).contains(k)));
+}
+
+#[test]
+fn event_time() {
+    let gen = DataGen::<i32, _>::new(Standard, 4.0, 0);
+    let times = gen.take(5).map(|(t, _)| t).collect::<std::vec::Vec<_>>();
+    for w in times.windows(2) {
+        assert_eq!(w[1] - w[0], Duration(time::Duration::milliseconds(250)));
+    }
+}
+
+#[test]
+fn uniform() {
+    let gen = DataGen::<i64, _>::new(Dist::uniform(-5.0, 5.0), f64::INFINITY, 1);
+    let mut counts = [0; 10];
+    for (_, x) in gen.take(1000) {
+        assert!((-5..5).contains(&x));
+        counts[(x + 5) as usize] += 1;
+    }
+    // Truncating towards zero would give 0 twice the share of any other value.
+    assert!(counts.iter().all(|n| (50..150).contains(n)), "{:?}", counts);
+}
+
+fn rate_limited(ctx: Context) {
+    let expected = DataGen::<i32, _>::new(Dist::uniform(0.0, 100.0), 100.0, 3)
+        .take(20)
+        .collect::<std::vec::Vec<_>>();
+    let gen = DataGen::<i32, _>::new(Dist::uniform(0.0, 100.0), 100.0, 3);
+    let start = Instant::now();
+    let s: Pullable<Event> = datagen(gen, Some(20), function!(event), ctx);
+    let events = block_on(s.try_collect::<std::vec::Vec<_>>()).unwrap();
+    // The last of 20 events at 100 events per second is due after 190 milliseconds.
+    assert!(start.elapsed() >= std::time::Duration::from_millis(190));
+    let events = events
+        .into_iter()
+        .map(|e| (e.time, e.value))
+        .collect::<std::vec::Vec<_>>();
+    assert_eq!(events, expected);
+}
+
+#[rewrite(main)]
+#[test]
+fn task() {
+    rate_limited();
+}
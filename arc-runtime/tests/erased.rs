@@ -0,0 +1,72 @@
+#![allow(unused)]
+
+use arc_runtime::data::erased::sendable;
+use arc_runtime::data::erased::TypeMismatch;
+use arc_runtime::data::erased::UneraseError;
+use arc_runtime::prelude::*;
+
+#[rewrite]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+fn checked(ctx: Context) {
+    let e = Erased::erase(5i32, ctx);
+    assert!(Erased::is::<i32>(e.clone(), ctx));
+    assert_eq!(Erased::unerase::<i32>(e.clone(), ctx), Ok(5));
+    let err = Erased::unerase::<i64>(e, ctx).unwrap_err();
+    assert_eq!(
+        err,
+        UneraseError::TypeMismatch(TypeMismatch {
+            expected: "i64",
+            found: "i32".to_string(),
+        })
+    );
+}
+
+/// Values of types which have not been erased by this process have no decoders, so they stay
+/// encoded until they are unerased.
+fn undecoded(ctx: Context) {
+    let received = |tag: &str, payload: &[u8]| -> Erased {
+        sendable::Erased {
+            tag: tag.to_string(),
+            payload: payload.into(),
+        }
+        .into_sharable(ctx)
+    };
+    let e = received("u16", b"7");
+    assert!(Erased::is::<u16>(e.clone(), ctx));
+    assert!(!Erased::is::<u8>(e.clone(), ctx));
+    assert_eq!(&*e.clone().into_sendable(ctx).payload, b"7");
+    assert!(matches!(
+        Erased::unerase::<u8>(e.clone(), ctx),
+        Err(UneraseError::TypeMismatch(_))
+    ));
+    assert_eq!(Erased::unerase::<u16>(e, ctx), Ok(7));
+    let e = received("u64", b"\"seven\"");
+    assert!(matches!(
+        Erased::unerase::<u64>(e, ctx),
+        Err(UneraseError::Decode { .. })
+    ));
+    assert_eq!(<Vec<Point> as Tagged>::tag(), "Vec<erased::Point>");
+}
+
+fn conversion(p: Point, ctx: Context) {
+    let e = Erased::erase(p, ctx);
+    let e: Erased = e.into_sendable(ctx).into_sharable(ctx);
+    let p = Erased::unerase::<Point>(e, ctx).unwrap();
+    assert_eq!((p.x, p.y), (1, 2));
+}
+
+#[rewrite(main)]
+#[test]
+fn erased() {
+    checked();
+    undecoded();
+    let p: Point = new!(Point { x: 1, y: 2 });
+    let e: Erased = erase!(p, Point);
+    let q: Point = unerase!(e, Point);
+    assert_eq!(q.x, 1);
+    conversion(p);
+}
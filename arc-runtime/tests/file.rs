@@ -0,0 +1,74 @@
+#![allow(unused)]
+
+use arc_runtime::data::channels::local::multicast::Pullable;
+use arc_runtime::prelude::*;
+use arc_runtime::sink;
+use arc_runtime::source;
+
+use futures::TryStreamExt;
+
+use std::io::Read;
+
+#[rewrite]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+fn path(name: &str) -> std::string::String {
+    format!("{}/{}", env!("CARGO_TARGET_TMPDIR"), name)
+}
+
+/// Returns the points which are read from `s`, or `None` if reading failed.
+fn points(s: Pullable<Point>) -> std::option::Option<std::vec::Vec<(i32, i32)>> {
+    block_on(s.try_collect::<std::vec::Vec<_>>())
+        .ok()
+        .map(|v| v.into_iter().map(|p| (p.x, p.y)).collect())
+}
+
+fn csv_header(ctx: Context) {
+    std::fs::write(path("header.csv"), "y,x\n2,1\n4,3\n").unwrap();
+    std::fs::write(path("positional.csv"), "1,2\n3,4\n").unwrap();
+    let s: Pullable<Point> = source::file::csv(&path("header.csv"), true, ctx);
+    assert_eq!(points(s), Some(vec![(1, 2), (3, 4)]));
+    let s: Pullable<Point> = source::file::csv(&path("positional.csv"), false, ctx);
+    assert_eq!(points(s), Some(vec![(1, 2), (3, 4)]));
+    let s: Pullable<Point> = source::file::csv(&path("positional.csv"), false, ctx);
+    let mut done = sink::file::csv(s, &path("written.csv"), true, ctx);
+    assert!(matches!(block_on(done.pull(ctx)), Control::Finished));
+    let written = std::fs::read_to_string(path("written.csv")).unwrap();
+    assert_eq!(written, "x,y\n1,2\n3,4\n");
+}
+
+fn csv_to_json(ctx: Context) {
+    std::fs::write(path("points.csv"), "x,y\n1,2\n3,4\n").unwrap();
+    let s: Pullable<Point> = source::file::csv(&path("points.csv"), true, ctx);
+    let mut done = sink::file::json(s, &path("points.json.gz"), ctx);
+    assert!(matches!(block_on(done.pull(ctx)), Control::Finished));
+    let mut json = std::string::String::new();
+    flate2::read::GzDecoder::new(std::fs::File::open(path("points.json.gz")).unwrap())
+        .read_to_string(&mut json)
+        .unwrap();
+    assert_eq!(json, "{\"x\":1,\"y\":2}\n{\"x\":3,\"y\":4}\n");
+    let s: Pullable<Point> = source::file::json(&path("points.json.gz"), ctx);
+    assert_eq!(points(s), Some(vec![(1, 2), (3, 4)]));
+}
+
+fn malformed(ctx: Context) {
+    std::fs::write(path("malformed.csv"), "x,y\n1,2\n3,four\n").unwrap();
+    let s: Pullable<Point> = source::file::csv(&path("malformed.csv"), true, ctx);
+    assert_eq!(points(s), None);
+    let s: Pullable<Point> = source::file::json(&path("missing.json"), ctx);
+    assert_eq!(points(s), None);
+    let s: Pullable<Point> = source::file::csv(&path("malformed.csv"), true, ctx);
+    let mut done = sink::file::json(s, &path("malformed.json"), ctx);
+    assert!(matches!(block_on(done.pull(ctx)), Control::Failed(_)));
+}
+
+#[rewrite(main)]
+#[test]
+fn file() {
+    csv_header();
+    csv_to_json();
+    malformed();
+}
@@ -0,0 +1,60 @@
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+#[rewrite]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[rewrite]
+pub enum Shape {
+    ShapeCircle(f64),
+    ShapeSquare(Point),
+}
+
+fn check(s: String, expected: &str) {
+    let s: &str = &s.0;
+    assert_eq!(s, expected);
+}
+
+fn interpolate(ctx: Context) {
+    let hello = String::from_str("hello", ctx);
+    let world = String::from_str("world", ctx);
+    let s = format("{} {}, 1+2 = {}", &[&hello, &world, &(1 + 2)], ctx);
+    check(s, "hello world, 1+2 = 3");
+    check(format("{{{}}}", &[&'x'], ctx), "{x}");
+}
+
+fn specs(ctx: Context) {
+    check(format("[{:5}]", &[&42], ctx), "[   42]");
+    check(format("[{:<5}]", &[&42], ctx), "[42   ]");
+    check(format("[{:^6}]", &[&true], ctx), "[ true ]");
+    check(format("[{:05}]", &[&-7], ctx), "[-0007]");
+    check(format("[{:.2}]", &[&3.14159], ctx), "[3.14]");
+    check(format("[{:>8.3}]", &[&2.5f32], ctx), "[   2.500]");
+    let s = String::from_str("abc", ctx);
+    check(format("[{:?}]", &[&s], ctx), "[\"abc\"]");
+    check(format("[{:>4.2}]", &[&s], ctx), "[  ab]");
+}
+
+fn values(p: Point, c: Shape, ctx: Context) {
+    let v: Vec<i32> = Vec::new(ctx);
+    Vec::push(v.clone(), 1, ctx);
+    check(format("{:?}", &[&v], ctx), &std::format!("{:?}", v));
+    check(format("{}", &[&(1, 'a')], ctx), "(1, 'a')");
+    check(format("{}", &[&Duration::from_seconds(2, ctx)], ctx), "2s");
+    check(format("{:?}", &[&p], ctx), &std::format!("{:?}", p));
+    check(format("{}", &[&c], ctx), &std::format!("{:?}", c));
+}
+
+#[rewrite(main)]
+#[test]
+fn interpolation() {
+    interpolate();
+    specs();
+    let p: Point = new!(Point { x: 1, y: 2 });
+    let c: Shape = enwrap!(ShapeSquare, p);
+    values(p, c);
+}
@@ -0,0 +1,288 @@
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+/// Sends a value through JSON and then through bincode, which is not self-describing. The
+/// value which is read back from JSON refers to its nodes by number when it is sent again.
+fn send<T: Sharable>(value: T, ctx: Context) -> <T::T as DynSendable>::T {
+    let text = serde_json::to_string(&value.into_sendable(ctx)).unwrap();
+    let value: T::T = serde_json::from_str(&text).unwrap();
+    let bytes = bincode::serialize(&value).unwrap();
+    let value: T::T = bincode::deserialize(&bytes).unwrap();
+    value.into_sharable(ctx)
+}
+
+mod deep {
+    use arc_runtime::prelude::*;
+
+    #[rewrite]
+    pub enum List {
+        ListCons(Cons),
+        ListNil(unit),
+    }
+
+    #[rewrite]
+    pub struct Cons {
+        pub v: i32,
+        pub t: List,
+    }
+
+    #[rewrite]
+    fn length(l: List) -> i32 {
+        let mut l: List = l;
+        let mut n: i32 = 0;
+        while is!(ListCons, l) {
+            let h: Cons = unwrap!(ListCons, l);
+            assert_eq!(h.v, 9999 - n);
+            n += 1;
+            l = h.t.clone();
+        }
+        n
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let mut l: List = enwrap!(ListNil, unit);
+        for i in 0..10000 {
+            let h: Cons = new!(Cons { v: i, t: l });
+            l = enwrap!(ListCons, h);
+        }
+        let s: <List as DynSharable>::T = l.into_sendable(ctx);
+        assert_eq!(length(s.into_sharable(ctx)), 10000);
+        assert_eq!(length(super::send(l)), 10000);
+    }
+}
+
+mod shared {
+    use arc_runtime::prelude::*;
+
+    #[rewrite]
+    pub enum Tree {
+        TreeBranch(Branch),
+        TreeLeaf(i32),
+    }
+
+    #[rewrite]
+    pub struct Branch {
+        pub l: Tree,
+        pub r: Tree,
+    }
+
+    #[rewrite]
+    fn depth(t: Tree) -> i32 {
+        let mut t: Tree = t;
+        let mut depth: i32 = 0;
+        while is!(TreeBranch, t) {
+            let b: Branch = unwrap!(TreeBranch, t);
+            assert!(&*b.l.0 as *const _ == &*b.r.0 as *const _);
+            depth += 1;
+            t = b.l.clone();
+        }
+        assert_eq!(unwrap!(TreeLeaf, t), 1);
+        depth
+    }
+
+    fn size(t: Tree, ctx: Context) -> usize {
+        serde_json::to_string(&t.into_sendable(ctx)).unwrap().len()
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        // A tree with 2^64 leaves, but only 65 distinct nodes.
+        let mut t: Tree = enwrap!(TreeLeaf, 1);
+        for _ in 0..64 {
+            let b: Branch = new!(Branch { l: t, r: t });
+            t = enwrap!(TreeBranch, b);
+        }
+        let s: <Tree as DynSharable>::T = t.into_sendable(ctx);
+        assert_eq!(depth(s.into_sharable(ctx)), 64);
+        assert!(size(t) < 10000);
+        assert_eq!(depth(super::send(t)), 64);
+    }
+}
+
+mod cyclic {
+    use arc_runtime::prelude::*;
+
+    #[rewrite]
+    pub enum Ring {
+        RingLink(Link),
+        RingEnd(unit),
+    }
+
+    #[rewrite]
+    pub struct Link {
+        pub v: i32,
+        pub next: Cell<Ring>,
+    }
+
+    #[rewrite]
+    fn check(r: Ring) {
+        let a: Link = unwrap!(RingLink, r);
+        let b: Link = unwrap!(RingLink, Cell::get(a.next.clone()));
+        assert_eq!((a.v, b.v), (0, 1));
+        let r2: Ring = Cell::get(b.next.clone());
+        assert!(&*r.0 as *const _ == &*r2.0 as *const _);
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let c0: Cell<Ring> = Cell::new(enwrap!(RingEnd, unit));
+        let c1: Cell<Ring> = Cell::new(enwrap!(RingEnd, unit));
+        let a: Link = new!(Link { v: 0, next: c0 });
+        let b: Link = new!(Link { v: 1, next: c1 });
+        let ra: Ring = enwrap!(RingLink, a);
+        let rb: Ring = enwrap!(RingLink, b);
+        Cell::set(c0, rb);
+        Cell::set(c1, ra);
+        let s: <Ring as DynSharable>::T = ra.into_sendable(ctx);
+        check(s.into_sharable(ctx));
+        check(super::send(ra));
+    }
+}
+
+mod structs {
+    use arc_runtime::prelude::*;
+
+    // A cycle which only passes through structs.
+    #[rewrite]
+    pub struct Node {
+        pub v: i32,
+        pub next: Vec<Node>,
+    }
+
+    #[rewrite]
+    fn check(n: Node) {
+        assert_eq!(n.v, 1);
+        let m: Node = Option::unwrap(Vec::get(n.next.clone(), 0));
+        assert!(&*n.0 as *const _ == &*m.0 as *const _);
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let v: Vec<Node> = Vec::new();
+        let n: Node = new!(Node { v: 1, next: v });
+        Vec::push(n.next.clone(), n);
+        let s: <Node as DynSharable>::T = n.into_sendable(ctx);
+        check(s.into_sharable(ctx));
+        check(super::send(n));
+    }
+}
+
+mod enums {
+    use arc_runtime::prelude::*;
+
+    // A cycle which only passes through an enum.
+    #[rewrite]
+    pub enum Tree {
+        TreeNode(Vec<Tree>),
+        TreeLeaf(i32),
+    }
+
+    #[rewrite]
+    fn check(t: Tree) {
+        let v: Vec<Tree> = unwrap!(TreeNode, t);
+        let u: Tree = Option::unwrap(Vec::get(v, 0));
+        assert!(&*t.0 as *const _ == &*u.0 as *const _);
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let v: Vec<Tree> = Vec::new();
+        let t: Tree = enwrap!(TreeNode, v);
+        Vec::push(v, t);
+        let s: <Tree as DynSharable>::T = t.into_sendable(ctx);
+        check(s.into_sharable(ctx));
+        check(super::send(t));
+    }
+}
+
+mod parents {
+    use arc_runtime::prelude::*;
+
+    #[rewrite]
+    pub struct Node {
+        pub v: i32,
+        pub next: Vec<Node>,
+    }
+
+    #[rewrite]
+    fn check(n: Node) {
+        let a: Node = Option::unwrap(Vec::get(n.next.clone(), 0));
+        let b: Node = Option::unwrap(Vec::get(n.next.clone(), 1));
+        let c: Node = Option::unwrap(Vec::get(a.next.clone(), 0));
+        let d: Node = Option::unwrap(Vec::get(b.next.clone(), 0));
+        assert_eq!((n.v, a.v, b.v, c.v), (0, 1, 2, 3));
+        assert!(&*c.0 as *const _ == &*d.0 as *const _);
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        // A node which is shared by two parents, neither of which is an ancestor of the other.
+        let v: Vec<Node> = Vec::new();
+        let c: Node = new!(Node { v: 3, next: v });
+        let v: Vec<Node> = vector![c,];
+        let a: Node = new!(Node { v: 1, next: v });
+        let v: Vec<Node> = vector![c,];
+        let b: Node = new!(Node { v: 2, next: v });
+        let v: Vec<Node> = vector![a, b,];
+        let n: Node = new!(Node { v: 0, next: v });
+        let s: <Node as DynSharable>::T = n.into_sendable(ctx);
+        check(s.into_sharable(ctx));
+        check(super::send(n));
+    }
+}
+
+mod references {
+    use arc_runtime::data::graphs::Unresolved;
+    use arc_runtime::prelude::*;
+
+    #[rewrite]
+    pub struct Node {
+        pub v: i32,
+        pub next: Vec<Node>,
+    }
+
+    type Sendable = <Node as DynSharable>::T;
+
+    #[rewrite]
+    fn check(n: Node) {
+        let m: Node = Option::unwrap(Vec::get(n.next.clone(), 0));
+        let k: Node = Option::unwrap(Vec::get(m.next.clone(), 0));
+        assert_eq!((n.v, m.v), (1, 2));
+        assert!(&*n.0 as *const _ == &*k.0 as *const _);
+    }
+
+    /// Reads a node which is referenced before it is listed, and which refers back to the node
+    /// that references it.
+    fn forward(ctx: Context) {
+        let text = r#"[{"v":1,"next":[{"Ref":1}]},{"v":2,"next":[{"Ref":0}]}]"#;
+        let s: Sendable = serde_json::from_str(text).unwrap();
+        let root = s.0.node().unwrap();
+        assert!(matches!(root.next.0[0].0.node(), Err(Unresolved(1))));
+        // A nested node cannot be written outside of the value which contains it.
+        assert!(serde_json::to_string(&root.next.0[0]).is_err());
+        check(s.into_sharable(ctx), ctx);
+    }
+
+    #[test]
+    fn errors() {
+        let e = serde_json::from_str::<Sendable>(r#"[{"v":1,"next":[{"Ref":1}]}]"#).unwrap_err();
+        assert!(e.to_string().contains("unknown node 1"));
+        let text = r#"[{"v":1,"next":[]},{"v":2,"next":[]}]"#;
+        let e = serde_json::from_str::<Sendable>(text).unwrap_err();
+        assert!(e.to_string().contains("before any reference"));
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        forward();
+    }
+}
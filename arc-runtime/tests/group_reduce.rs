@@ -0,0 +1,188 @@
+#![allow(unused)]
+
+use arc_runtime::data::channels::local::multicast as clm;
+use arc_runtime::data::channels::local::multicast::Pullable;
+use arc_runtime::operators::aggregate::*;
+use arc_runtime::operators::window::Emit;
+use arc_runtime::prelude::*;
+
+use futures::TryStreamExt;
+
+declare_functions!(parity, zero, identity, widen, product, row, stats, timestamp);
+
+#[rewrite]
+pub struct Row {
+    pub k: i32,
+    pub sum: i32,
+    pub count: u64,
+}
+
+#[rewrite]
+fn parity(x: i32) -> i32 {
+    x % 2
+}
+
+#[rewrite]
+pub struct Stats {
+    pub min: i32,
+    pub max: i32,
+    pub avg: f64,
+    pub first: i32,
+    pub last: i32,
+    pub product: i32,
+}
+
+#[rewrite]
+fn zero(x: i32) -> i32 {
+    0
+}
+
+#[rewrite]
+fn identity(x: i32) -> i32 {
+    x
+}
+
+#[rewrite]
+fn widen(x: i32) -> i64 {
+    x as i64
+}
+
+#[rewrite]
+fn product(acc: i32, x: i32) -> i32 {
+    acc * x
+}
+
+#[rewrite]
+fn row(k: i32, agg: (i32, u64)) -> Row {
+    new!(Row {
+        k: k,
+        sum: agg.0,
+        count: agg.1
+    })
+}
+
+#[rewrite]
+fn stats(k: i32, agg: (i32, i32, f64, i32, i32, i32)) -> Stats {
+    new!(Stats {
+        min: agg.0,
+        max: agg.1,
+        avg: agg.2,
+        first: agg.3,
+        last: agg.4,
+        product: agg.5
+    })
+}
+
+#[rewrite(main)]
+#[test]
+fn group_reduce_vec() {
+    let v: Vec<i32> = vector![1, 2, 3, 4, 5,];
+    let k: function!((i32) -> i32) = function!(parity);
+    let f: function!((i32) -> i32) = function!(identity);
+    let g: function!((i32, (i32, u64)) -> Row) = function!(row);
+    let r: Vec<Row> = Vec::group_reduce(v, k, (sum(f), count()), g);
+    let odd: Row = Option::unwrap(Vec::get(r, 0));
+    let even: Row = Option::unwrap(Vec::get(r, 1));
+    assert_eq!(Vec::len(r), 2);
+    assert_eq!((odd.k, odd.sum, odd.count), (1, 9, 3));
+    assert_eq!((even.k, even.sum, even.count), (0, 6, 2));
+}
+
+#[rewrite(main)]
+#[test]
+fn builtin_aggregators() {
+    let v: Vec<i32> = vector![3, 1, 4, 1, 5,];
+    let k: function!((i32) -> i32) = function!(zero);
+    let f: function!((i32) -> i32) = function!(identity);
+    let p: function!((i32, i32) -> i32) = function!(product);
+    let g: function!((i32, (i32, i32, f64, i32, i32, i32)) -> Stats) = function!(stats);
+    let r: Vec<Stats> = Vec::group_reduce(
+        v,
+        k,
+        (min(f), max(f), avg(f), first(f), last(f), fold(1, p)),
+        g,
+    );
+    let x: Stats = Option::unwrap(Vec::get(r, 0));
+    assert_eq!((x.min, x.max, x.first, x.last, x.product), (1, 5, 3, 5, 60));
+    assert!((x.avg - 2.8).abs() < 1e-9);
+}
+
+/// Averages 64-bit integers, which do not implement `Into<f64>`.
+fn avg_i64(ctx: Context) {
+    let f: function!((i32) -> i64) = function!(widen);
+    let a = avg(f, ctx);
+    let acc = [3, 1, 4, 1, 5]
+        .into_iter()
+        .fold(Aggregator::<i32>::init(&a, ctx), |acc, x| {
+            a.update(acc, x, ctx)
+        });
+    assert!((Aggregator::<i32>::finish(&a, acc, ctx) - 2.8).abs() < 1e-9);
+}
+
+#[rewrite(main)]
+#[test]
+fn builtin_aggregators_i64() {
+    avg_i64();
+}
+
+#[rewrite]
+fn timestamp(x: i32) -> DateTime {
+    DateTime::from_unix_timestamp(x as i64)
+}
+
+/// Groups `input` by parity as a stream, and returns the rows which are emitted.
+fn rows(input: &[i32], emit: Emit<i32>, ctx: Context) -> std::vec::Vec<(i32, i32, u64)> {
+    let s: Pullable<i32> = clm::from_stream(futures::stream::iter(input.to_vec()), ctx);
+    let f: function!((i32) -> i32) = function!(identity);
+    let s: Pullable<Row> = s.group_reduce(
+        function!(parity),
+        (sum(f, ctx), count(ctx)),
+        emit,
+        function!(row),
+        ctx,
+    );
+    block_on(s.try_collect::<std::vec::Vec<_>>())
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.k, r.sum, r.count))
+        .collect()
+}
+
+fn emit_final(ctx: Context) {
+    assert_eq!(
+        rows(&[1, 2, 3, 4, 5, 6], Emit::Final, ctx),
+        vec![(1, 9, 3), (0, 12, 3)]
+    );
+}
+
+fn emit_incremental(ctx: Context) {
+    assert_eq!(
+        rows(&[1, 2, 3], Emit::Incremental, ctx),
+        vec![(1, 1, 1), (0, 2, 1), (1, 4, 2)]
+    );
+}
+
+fn emit_count(ctx: Context) {
+    assert_eq!(
+        rows(&[1, 2, 3, 4, 5], Emit::Count(2), ctx),
+        vec![(1, 1, 1), (0, 2, 1), (1, 3, 1), (0, 4, 1), (1, 5, 1)]
+    );
+}
+
+fn emit_tumbling(ctx: Context) {
+    let length = Duration::from_seconds(10, ctx);
+    let emit = Emit::tumbling(length, function!(timestamp), ctx);
+    assert_eq!(
+        rows(&[1, 2, 3, 11, 25, 28], emit, ctx),
+        vec![(1, 4, 2), (0, 2, 1), (1, 11, 1), (1, 25, 1), (0, 28, 1)]
+    );
+}
+
+#[rewrite(main)]
+#[test]
+fn group_reduce_stream() {
+    emit_final();
+    emit_incremental();
+    emit_count();
+    emit_tumbling();
+}
@@ -0,0 +1,80 @@
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+#[rewrite(main)]
+#[test]
+fn map() {
+    let m: Map<String, i32> = Map::new();
+    let a: String = String::from_str("a");
+    let b: String = String::from_str("b");
+    Map::insert(m, a, 1);
+    Map::insert(m, b, 2);
+    assert_eq!(
+        Option::unwrap_or(Map::insert(m, String::from_str("a"), 3), 0),
+        1
+    );
+    assert_eq!(Option::unwrap_or(Map::get(m, String::from_str("a")), 0), 3);
+    assert!(Map::contains(m, String::from_str("b")));
+    assert_eq!(
+        Option::unwrap_or(Map::remove(m, String::from_str("b")), 0),
+        2
+    );
+    assert!(!Map::contains(m, String::from_str("b")));
+    assert_eq!(Map::len(m), 1);
+    let e: Vec<(String, i32)> = Map::iter(m);
+    assert_eq!(Vec::len(e), 1);
+}
+
+#[rewrite(main)]
+#[test]
+fn set() {
+    let s: Set<i32> = Set::new();
+    assert!(Set::insert(s, 1));
+    assert!(Set::insert(s, 2));
+    assert!(!Set::insert(s, 1));
+    assert!(Set::remove(s, 2));
+    assert!(!Set::contains(s, 2));
+    Set::insert(s, 3);
+    let v: Vec<i32> = Set::iter(s);
+    assert_eq!(v.as_slice(ctx), &[1, 3]);
+}
+
+#[rewrite(main)]
+#[test]
+fn conversion() {
+    let m: Map<i32, Vec<i32>> = Map::new();
+    Map::insert(m, 1, vector![1, 2, 3,]);
+    Map::insert(m, 2, vector![4,]);
+    let m: Map<i32, Vec<i32>> = m.into_sendable(ctx).into_sharable(ctx);
+    assert_eq!(Map::len(m), 2);
+    assert_eq!(Vec::len(Option::unwrap_or(Map::get(m, 1), Vec::new())), 3);
+    let s: Set<i32> = Set::new();
+    Set::insert(s, 7);
+    let s: Set<i32> = s.into_sendable(ctx).into_sharable(ctx);
+    assert!(Set::contains(s, 7));
+}
+
+/// Checks that the keys 99 to 0 come out in insertion order, except for 50 which was removed.
+fn in_order(e: Vec<(i32, i32)>, v: Vec<i32>, ctx: Context) {
+    let keys: std::vec::Vec<i32> = (0..100).rev().filter(|k| *k != 50).collect();
+    let e: std::vec::Vec<i32> = e.as_slice(ctx).iter().map(|e| e.0).collect();
+    assert_eq!(e, keys);
+    assert_eq!(v.as_slice(ctx), keys.as_slice());
+}
+
+#[rewrite(main)]
+#[test]
+fn order() {
+    let m: Map<i32, i32> = Map::new();
+    let s: Set<i32> = Set::new();
+    for i in 0..100 {
+        Map::insert(m, 99 - i, i);
+        Set::insert(s, 99 - i);
+    }
+    Map::remove(m, 50);
+    Set::remove(s, 50);
+    let m: Map<i32, i32> = m.into_sendable(ctx).into_sharable(ctx);
+    let s: Set<i32> = s.into_sendable(ctx).into_sharable(ctx);
+    in_order(Map::iter(m), Set::iter(s));
+}
@@ -0,0 +1,62 @@
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+#[rewrite(main)]
+#[test]
+fn bigint() {
+    let a: BigInt = BigInt::from_i64(i64::MAX);
+    let b: BigInt = BigInt::mul(a, a);
+    let c: BigInt = Option::unwrap_or(
+        BigInt::parse(String::from_str("85070591730234615847396907784232501249")),
+        a,
+    );
+    assert!(BigInt::eq(b, c));
+    assert!(Option::is_none(BigInt::to_i64(b)));
+    let d: BigInt = Option::unwrap_or(BigInt::div(b, a), a);
+    assert_eq!(Option::unwrap_or(BigInt::to_i64(d), 0), i64::MAX);
+    assert!(Option::is_none(BigInt::div(a, BigInt::from_i64(0))));
+    assert!(BigInt::lt(BigInt::neg(b), a));
+    assert_eq!(BigInt::signum(BigInt::sub(a, b)), -1);
+    let e: BigInt = b.into_sendable(ctx).into_sharable(ctx);
+    assert!(BigInt::eq(e, b));
+    assert_eq!(
+        format("{}", &[&BigInt::pow(BigInt::from_i64(10), 20)]),
+        String::from_str("100000000000000000000")
+    );
+}
+
+#[rewrite(main)]
+#[test]
+fn decimal() {
+    let a: Decimal = Option::unwrap_or(
+        Decimal::parse(String::from_str("0.1")),
+        Decimal::from_i64(0),
+    );
+    let b: Decimal = Option::unwrap_or(
+        Decimal::parse(String::from_str("0.2")),
+        Decimal::from_i64(0),
+    );
+    let c: Decimal = Option::unwrap_or(
+        Decimal::parse(String::from_str("0.30")),
+        Decimal::from_i64(0),
+    );
+    assert!(Decimal::eq(Option::unwrap(Decimal::add(a, b)), c));
+    let max: Decimal = Option::unwrap(Decimal::parse(String::from_str(
+        "79228162514264337593543950335",
+    )));
+    assert!(Option::is_none(Decimal::add(max, max)));
+    assert!(Option::is_none(Decimal::mul(max, max)));
+    assert_eq!(Decimal::scale(c), 2);
+    let d: Decimal = Option::unwrap_or(Decimal::div(Decimal::from_i64(2), Decimal::from_i64(3)), a);
+    assert!(String::eq(
+        Decimal::to_string(Decimal::round(d, 2)),
+        String::from_str("0.67")
+    ));
+    assert!(Option::is_none(Decimal::div(a, Decimal::from_i64(0))));
+    assert!(Option::is_none(Decimal::parse(String::from_str("abc"))));
+    assert!(Decimal::gt(c, a));
+    let e: Decimal = c.into_sendable(ctx).into_sharable(ctx);
+    assert!(Decimal::eq(e, c));
+    assert_eq!(Decimal::to_f64(e), 0.3);
+}
@@ -0,0 +1,98 @@
+#![allow(unused)]
+
+mod option {
+    use arc_runtime::prelude::*;
+
+    declare_functions!(double, half);
+
+    #[rewrite]
+    fn double(x: i32) -> i32 {
+        x + x
+    }
+
+    #[rewrite]
+    fn half(x: i32) -> Option<i32> {
+        if x % 2 == 0 {
+            Option::some(x / 2)
+        } else {
+            Option::none()
+        }
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let a: Option<i32> = Option::some(3);
+        let b: Option<i32> = Option::none();
+        assert!(Option::is_some(a));
+        assert!(Option::is_none(b));
+        assert!(is!(OptionSome, a));
+        assert_eq!(unwrap!(OptionSome, a), 3);
+        let c: Option<i32> = Option::map(a, function!(double));
+        assert_eq!(Option::unwrap_or(c, 0), 6);
+        assert!(Option::is_none(Option::map(b, function!(double))));
+        let d: Option<i32> = Option::and_then(c, function!(half));
+        assert_eq!(Option::unwrap_or(d, 0), 3);
+        assert!(Option::is_none(Option::and_then(d, function!(half))));
+        let e: Option<i32> = a.into_sendable(ctx).into_sharable(ctx);
+        assert_eq!(Option::unwrap_or(e, 0), 3);
+    }
+}
+
+mod result {
+    use arc_runtime::prelude::*;
+
+    declare_functions!(double, check);
+
+    #[rewrite]
+    fn double(x: i32) -> i32 {
+        x + x
+    }
+
+    #[rewrite]
+    fn check(x: i32) -> Result<i32, String> {
+        if x < 10 {
+            Result::ok(x)
+        } else {
+            Result::err(String::from_str("too large"))
+        }
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let a: Result<i32, String> = Result::ok(3);
+        assert!(Result::is_ok(a));
+        assert!(is!(ResultOk, a));
+        let b: Result<i32, String> = Result::map(a, function!(double));
+        assert_eq!(Result::unwrap_or(b, 0), 6);
+        let c: Result<i32, String> = Result::and_then(b, function!(check));
+        assert_eq!(Result::unwrap_or(c, 0), 6);
+        let d: Result<i32, String> =
+            Result::and_then(Result::map(c, function!(double)), function!(check));
+        assert!(Result::is_err(d));
+        let e: String = unwrap!(ResultErr, d);
+        assert!(String::eq(e, String::from_str("too large")));
+        let f: Result<i32, String> = d.into_sendable(ctx).into_sharable(ctx);
+        assert_eq!(Result::unwrap_or(f, 0), 0);
+    }
+}
+
+mod fallible {
+    use arc_runtime::prelude::*;
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let v: Vec<i32> = vector![1, 2,];
+        assert_eq!(Option::unwrap_or(Vec::get(v, 1), 0), 2);
+        assert!(Option::is_none(Vec::get(v, 2)));
+        assert!(Option::is_none(Vec::remove(v, 2)));
+        assert_eq!(Option::unwrap_or(Vec::pop(v), 0), 2);
+        assert_eq!(Option::unwrap_or(Vec::pop(v), 0), 1);
+        assert!(Option::is_none(Vec::pop(v)));
+        let s: String = String::from_str("abc");
+        assert_eq!(Option::unwrap_or(String::remove(s, 1), ' '), 'b');
+        assert!(Option::is_none(String::remove(s, 2)));
+    }
+}
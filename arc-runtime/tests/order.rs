@@ -0,0 +1,134 @@
+#![allow(unused)]
+
+use arc_runtime::data::channels::local::multicast as clm;
+use arc_runtime::data::channels::local::multicast::Pullable;
+use arc_runtime::operators::order::Order;
+use arc_runtime::operators::window::Emit;
+use arc_runtime::prelude::*;
+
+use futures::TryStreamExt;
+
+declare_functions!(identity, modulo, inverse);
+
+#[rewrite]
+fn identity(x: i32) -> i32 {
+    x
+}
+
+#[rewrite]
+fn modulo(x: i32) -> i32 {
+    x % 3
+}
+
+#[rewrite]
+fn inverse(x: i32) -> f64 {
+    if x == 0 {
+        f64::NAN
+    } else {
+        1.0 / x as f64
+    }
+}
+
+#[rewrite(main)]
+#[test]
+fn order_by() {
+    let v: Vec<i32> = vector![5, 3, 4, 1, 2,];
+    let f: function!((i32) -> i32) = function!(identity);
+    let asc: Vec<i32> = Vec::order_by(v, f, Order::Asc);
+    let desc: Vec<i32> = Vec::order_by(v, f, Order::Desc);
+    assert_eq!(asc.as_slice(ctx), &[1, 2, 3, 4, 5]);
+    assert_eq!(desc.as_slice(ctx), &[5, 4, 3, 2, 1]);
+}
+
+#[rewrite(main)]
+#[test]
+fn order_by_is_stable() {
+    let v: Vec<i32> = vector![5, 3, 4, 1, 2, 6,];
+    let f: function!((i32) -> i32) = function!(modulo);
+    let v: Vec<i32> = Vec::order_by(v, f, Order::Asc);
+    assert_eq!(v.as_slice(ctx), &[3, 6, 4, 1, 5, 2]);
+}
+
+#[rewrite(main)]
+#[test]
+fn top_k() {
+    let v: Vec<i32> = vector![5, 3, 4, 1, 2,];
+    let f: function!((i32) -> i32) = function!(identity);
+    let top: Vec<i32> = Vec::top_k(v, f, Order::Desc, 2);
+    let bottom: Vec<i32> = Vec::top_k(v, f, Order::Asc, 3);
+    let none: Vec<i32> = Vec::top_k(v, f, Order::Asc, 0);
+    let all: Vec<i32> = Vec::top_k(v, f, Order::Asc, 10);
+    assert_eq!(top.as_slice(ctx), &[5, 4]);
+    assert_eq!(bottom.as_slice(ctx), &[1, 2, 3]);
+    assert!(none.as_slice(ctx).is_empty());
+    assert_eq!(all.as_slice(ctx), &[1, 2, 3, 4, 5]);
+}
+
+#[rewrite(main)]
+#[test]
+fn order_by_nan() {
+    let v: Vec<i32> = vector![2, 0, 1, 4,];
+    let f: function!((i32) -> f64) = function!(inverse);
+    let asc: Vec<i32> = Vec::order_by(v, f, Order::Asc);
+    let desc: Vec<i32> = Vec::order_by(v, f, Order::Desc);
+    assert_eq!(asc.as_slice(ctx), &[4, 2, 1, 0]);
+    assert_eq!(desc.as_slice(ctx), &[0, 1, 2, 4]);
+}
+
+/// Ranks `input` as a stream, and returns the vectors which are emitted.
+fn rank_stream(
+    input: &[i32],
+    order: Order,
+    limit: std::option::Option<usize>,
+    emit: Emit<i32>,
+    ctx: Context,
+) -> std::vec::Vec<std::vec::Vec<i32>> {
+    let s: Pullable<i32> = clm::from_stream(futures::stream::iter(input.to_vec()), ctx);
+    let f: function!((i32) -> i32) = function!(identity);
+    let s: Pullable<Vec<i32>> = match limit {
+        Some(k) => s.top_k(f, order, k, emit, ctx),
+        None => s.order_by(f, order, emit, ctx),
+    };
+    block_on(s.try_collect::<std::vec::Vec<_>>())
+        .unwrap()
+        .into_iter()
+        .map(|v| v.as_slice(ctx).to_vec())
+        .collect()
+}
+
+fn order_by_final(ctx: Context) {
+    assert_eq!(
+        rank_stream(&[5, 3, 4, 1, 2], Order::Asc, None, Emit::Final, ctx),
+        vec![vec![1, 2, 3, 4, 5]]
+    );
+}
+
+fn order_by_count(ctx: Context) {
+    assert_eq!(
+        rank_stream(&[5, 3, 4, 1, 2], Order::Desc, None, Emit::Count(2), ctx),
+        vec![vec![5, 3], vec![4, 1], vec![2]]
+    );
+}
+
+fn top_k_final(ctx: Context) {
+    assert_eq!(
+        rank_stream(&[5, 3, 4, 1, 2], Order::Desc, Some(2), Emit::Final, ctx),
+        vec![vec![5, 4]]
+    );
+}
+
+fn top_k_incremental(ctx: Context) {
+    assert_eq!(
+        rank_stream(&[3, 1, 2], Order::Asc, Some(2), Emit::Incremental, ctx),
+        vec![vec![3], vec![1, 3], vec![1, 2]]
+    );
+}
+
+#[rewrite(main)]
+#[test]
+fn rank_streams() {
+    order_by_final();
+    order_by_count();
+    top_k_final();
+    top_k_incremental();
+}
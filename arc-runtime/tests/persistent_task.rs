@@ -52,7 +52,7 @@ mod map {
         }: State0,
         _cx: &mut PollContext,
         ctx: Context,
-    ) -> (Poll<()>, State) {
+    ) -> (Poll<Control<()>>, State) {
         pull_transition!(pull, a, State1 { a, b, f, pull });
     }
 
@@ -65,7 +65,7 @@ mod map {
         }: State1,
         cx: &mut PollContext,
         ctx: Context,
-    ) -> (Poll<()>, State) {
+    ) -> (Poll<Control<()>>, State) {
         let x = wait!(pull, cx, State1 { a, b, f, pull }, State3 {});
         let y = call_indirect!(f(x));
         push_transition!(push, b, y, State2 { a, b, f, push });
@@ -80,12 +80,16 @@ mod map {
         }: State2,
         cx: &mut PollContext,
         ctx: Context,
-    ) -> (Poll<()>, State) {
+    ) -> (Poll<Control<()>>, State) {
         wait!(push, cx, State2 { a, b, f, push }, State0 { a, b, f });
         transition!(State0 { a, b, f });
     }
 
-    fn transition3(State3 {}: State3, _cx: &mut PollContext, ctx: Context) -> (Poll<()>, State) {
+    fn transition3(
+        State3 {}: State3,
+        _cx: &mut PollContext,
+        ctx: Context,
+    ) -> (Poll<Control<()>>, State) {
         unreachable!()
     }
 }
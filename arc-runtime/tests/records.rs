@@ -0,0 +1,35 @@
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+declare_records!({k, v}, {k, w}, {k, ..});
+
+#[rewrite]
+fn get_k(r: record!(k: i32, ..)) -> i32 {
+    access!(r, k)
+}
+
+#[rewrite(main)]
+#[test]
+fn closed() {
+    let r: record!(v: i32, k: i32) = record!(v = 2, k = 1);
+    let s: record!(k: i32, v: i32) = r;
+    assert_eq!(access!(s, k), 1);
+    assert_eq!(access!(s, v), 2);
+    let t: record!(k: i32, v: i32) = r.into_sendable(ctx).into_sharable(ctx);
+    assert_eq!(access!(t, v), 2);
+}
+
+#[rewrite(main)]
+#[test]
+fn open() {
+    let r: record!(k: i32, v: i32) = record!(k = 1, v = 2);
+    let o: record!(k: i32, ..) = Open::open(r);
+    let k: i32 = call!(get_k(o));
+    assert_eq!(k, 1);
+    let o: record!(k: i32, ..) = o.into_sendable(ctx).into_sharable(ctx);
+    let c: record!(k: i32, v: i32) = Close::close(o).unwrap();
+    assert_eq!(access!(c, v), 2);
+    let w: std::result::Result<record!(k: i32, w: i32), _> = Close::close(o);
+    assert!(w.is_err());
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "dataframes")]
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+#[rewrite(main)]
+#[test]
+fn kernels() {
+    let a: Series = Series::from_vec(String::from_str("a"), vector![1, 2, 3,]);
+    let b: Series = Series::from_vec(String::from_str("b"), vector![3, 2, 1,]);
+    let c: Series = Series::add(a, b);
+    assert_eq!(Series::len(c), 3);
+    assert_eq!(Option::unwrap(Series::get::<i32>(c, 1)), 4);
+    assert!(Option::is_none(Series::get::<i32>(c, 3)));
+    let d: Series = Series::gt(a, b);
+    let d: Vec<bool> = Series::to_vec(d);
+    assert!(!Option::unwrap(Vec::get(d, 0)));
+    assert!(Option::unwrap(Vec::get(d, 2)));
+    assert!(String::eq(Series::name(a), String::from_str("a")));
+}
+
+#[rewrite(main)]
+#[test]
+fn aggregations() {
+    let a: Series = Series::from_vec(String::from_str("a"), vector![1.0, 4.0, 2.5,]);
+    assert_eq!(Series::sum::<f64>(a), 7.5);
+    assert_eq!(Series::mean(a), 2.5);
+    assert_eq!(Series::min::<f64>(a), 1.0);
+    assert_eq!(Series::max::<f64>(a), 4.0);
+}
@@ -0,0 +1,62 @@
+#![allow(unused)]
+
+use arc_runtime::data::channels::local::multicast as clm;
+use arc_runtime::prelude::*;
+
+use futures::SinkExt;
+use futures::StreamExt;
+use futures::TryStreamExt;
+
+fn sink_to_stream(ctx: Context) {
+    let (mut o0, o1) = clm::channel::<i32>(ctx);
+    block_on(async {
+        o0.send(1).await.unwrap();
+        o0.send(2).await.unwrap();
+        o0.send(3).await.unwrap();
+    });
+    drop(o0);
+    let v = block_on(o1.try_collect::<std::vec::Vec<_>>()).unwrap();
+    assert_eq!(v, vec![1, 2, 3]);
+}
+
+fn stream_then_pull(ctx: Context) {
+    let (o0, mut o1) = clm::channel::<i32>(ctx);
+    block_on(async {
+        o0.push(1, ctx).await;
+        o0.push(2, ctx).await;
+    });
+    drop(o0);
+    assert!(matches!(block_on(o1.next()), Some(Ok(1))));
+    assert!(matches!(block_on(o1.pull(ctx)), Control::Continue(2)));
+    assert!(matches!(block_on(o1.pull(ctx)), Control::Finished));
+}
+
+fn lagging_puller(ctx: Context) {
+    let (o0, mut o1) = clm::channel::<i32>(ctx);
+    block_on(async {
+        for i in 0..2 * clm::CAPACITY as i32 {
+            o0.push(i, ctx).await;
+        }
+    });
+    assert!(matches!(block_on(o1.pull(ctx)), Control::Failed(_)));
+    assert!(matches!(block_on(o1.pull(ctx)), Control::Continue(_)));
+}
+
+fn numbers(ctx: Context) -> clm::Pullable<i32> {
+    clm::from_stream(futures::stream::iter(0..3), ctx)
+}
+
+fn bridge(ctx: Context) {
+    let s = numbers(ctx).into_stream(ctx);
+    let v = block_on(s.try_collect::<std::vec::Vec<_>>()).unwrap();
+    assert_eq!(v, vec![0, 1, 2]);
+}
+
+#[rewrite(main)]
+#[test]
+fn stream() {
+    sink_to_stream();
+    stream_then_pull();
+    lagging_puller();
+    bridge();
+}
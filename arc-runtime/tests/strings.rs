@@ -0,0 +1,86 @@
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+#[rewrite(main)]
+#[test]
+fn split() {
+    let s: String = String::from_str("the quick  fox");
+    let v: Vec<String> = String::split(s, String::from_str(" "));
+    assert_eq!(Vec::len(v), 4);
+    assert!(String::eq(
+        Option::unwrap(Vec::get(v, 1)),
+        String::from_str("quick")
+    ));
+    assert!(String::is_empty(Option::unwrap(Vec::get(v, 2))));
+}
+
+#[rewrite(main)]
+#[test]
+fn search_and_replace() {
+    let s: String = String::from_str("  Hello, world!  ");
+    let t: String = String::trim(s);
+    assert!(String::starts_with(t, String::from_str("Hello")));
+    assert!(String::ends_with(t, String::from_str("!")));
+    assert_eq!(
+        Option::unwrap_or(String::find(t, String::from_str("world")), 0),
+        7
+    );
+    assert!(Option::is_none(String::find(t, String::from_str("moon"))));
+    let r: String = String::replace(t, String::from_str("world"), String::from_str("arc"));
+    assert!(String::eq(r, String::from_str("Hello, arc!")));
+    assert!(String::eq(
+        String::to_uppercase(r),
+        String::from_str("HELLO, ARC!")
+    ));
+}
+
+#[rewrite(main)]
+#[test]
+fn substring() {
+    let s: String = String::from_str("naïve café");
+    assert!(String::eq(
+        Option::unwrap(String::substring(s, 2, 5)),
+        String::from_str("ïve")
+    ));
+    assert!(String::eq(
+        Option::unwrap(String::substring(s, 6, 10)),
+        String::from_str("café")
+    ));
+    assert!(Option::is_none(String::substring(s, 6, 11)));
+    let i: u32 = Option::unwrap_or(String::find(s, String::from_str("café")), 0);
+    assert_eq!(i, 6);
+    assert!(String::eq(
+        Option::unwrap(String::substring(s, i, i + 4)),
+        String::from_str("café")
+    ));
+}
+
+#[rewrite(main)]
+#[test]
+fn numbers() {
+    assert_eq!(
+        Option::unwrap(String::parse_i64(String::from_str("-42"))),
+        -42
+    );
+    assert_eq!(
+        Option::unwrap(String::parse_u8(String::from_str("255"))),
+        255
+    );
+    assert!(Option::is_none(String::parse_u8(String::from_str("256"))));
+    assert!(Option::unwrap(String::parse_bool(String::from_str("true"))));
+    assert!((Option::unwrap(String::parse_f64(String::from_str("2.5"))) - 2.5).abs() < 1e-9);
+    assert!(String::eq(String::from_u64(7), String::from_str("7")));
+    assert!(String::eq(String::from_f32(0.5), String::from_str("0.5")));
+    assert!(String::eq(String::from_char('x'), String::from_str("x")));
+}
+
+#[rewrite(main)]
+#[test]
+fn compare() {
+    let a: String = String::from_str("apple");
+    let b: String = String::from_str("banana");
+    assert_eq!(String::cmp(a, b), -1);
+    assert!(String::lt(a, b));
+    assert_eq!(String::hash(a), String::hash(String::from_str("apple")));
+}
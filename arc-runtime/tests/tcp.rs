@@ -0,0 +1,134 @@
+#![allow(unused)]
+
+use arc_runtime::data::channels::local::multicast as clm;
+use arc_runtime::data::channels::local::multicast::Pullable;
+use arc_runtime::prelude::*;
+use arc_runtime::sink;
+use arc_runtime::source;
+use arc_runtime::source::tcp::Framing;
+use arc_runtime::source::tcp::Reconnect;
+
+use std::io::BufReader;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+
+#[rewrite]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+const RETRY: Reconnect = Reconnect {
+    attempts: 50,
+    delay: std::time::Duration::from_millis(20),
+};
+
+const FRAMED: Framing = Framing::LengthPrefixed { max_len: 1024 };
+
+#[test]
+fn framing() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let writer = std::thread::spawn(move || {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        Framing::Lines.write(&mut stream, b"hello").unwrap();
+        Framing::Lines.write(&mut stream, b"").unwrap();
+        FRAMED.write(&mut stream, b"a\nb").unwrap();
+    });
+    let (stream, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(stream);
+    let frame = Framing::Lines.read(&mut reader).unwrap();
+    assert_eq!(frame.as_deref(), Some(&b"hello"[..]));
+    let frame = Framing::Lines.read(&mut reader).unwrap();
+    assert_eq!(frame.as_deref(), Some(&b""[..]));
+    let frame = FRAMED.read(&mut reader).unwrap();
+    assert_eq!(frame.as_deref(), Some(&b"a\nb"[..]));
+    writer.join().unwrap();
+    assert_eq!(FRAMED.read(&mut reader).unwrap(), None);
+}
+
+#[test]
+fn malformed_frames() {
+    let small = Framing::LengthPrefixed { max_len: 2 };
+    let err = small.write(&mut std::vec::Vec::new(), b"abc").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let mut frame = std::vec::Vec::new();
+    FRAMED.write(&mut frame, b"abc").unwrap();
+    let err = small.read(&mut &frame[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = FRAMED.read(&mut &frame[..2]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    let err = FRAMED.read(&mut &frame[..5]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+/// Binds a listener to a port which is picked by the OS.
+fn listen() -> (TcpListener, std::string::String) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    (listener, addr)
+}
+
+/// Relays points from one socket to another, and returns the points which are written.
+fn relay(
+    lines: &'static [&'static str],
+    ctx: Context,
+) -> (Control<unit>, std::vec::Vec<(i32, i32)>) {
+    let (input, input_addr) = listen();
+    let (output, output_addr) = listen();
+    let writer = std::thread::spawn(move || {
+        let (mut stream, _) = input.accept().unwrap();
+        for line in lines {
+            // The source stops reading at the first malformed record.
+            let _ = writeln!(stream, "{}", line);
+        }
+    });
+    let reader = std::thread::spawn(move || {
+        let (stream, _) = output.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut points = std::vec::Vec::new();
+        while let Some(frame) = FRAMED.read(&mut reader).unwrap() {
+            let p: sendable_Point::Point = serde_json::from_slice(&frame).unwrap();
+            points.push((p.x, p.y));
+        }
+        points
+    });
+    let s: Pullable<Point> = source::tcp::json(&input_addr, Framing::Lines, RETRY, ctx);
+    let mut done = sink::tcp::json(s, &output_addr, FRAMED, RETRY, ctx);
+    let control = block_on(done.pull(ctx));
+    writer.join().unwrap();
+    (control, reader.join().unwrap())
+}
+
+fn source_sink(ctx: Context) {
+    let lines = &[r#"{"x":0,"y":0}"#, r#"{"x":1,"y":2}"#, r#"{"x":2,"y":4}"#];
+    let (control, points) = relay(lines, ctx);
+    assert!(matches!(control, Control::Finished));
+    assert_eq!(points, vec![(0, 0), (1, 2), (2, 4)]);
+}
+
+fn malformed_record(ctx: Context) {
+    let lines = &[r#"{"x":0,"y":0}"#, r#"{"x":1"#, r#"{"x":2,"y":4}"#];
+    let (control, points) = relay(lines, ctx);
+    assert!(matches!(control, Control::Failed(_)));
+    assert_eq!(points, vec![(0, 0)]);
+}
+
+fn unreachable_sink(ctx: Context) {
+    let (listener, addr) = listen();
+    drop(listener);
+    let p: sendable_Point::Point = sendable_Point::ConcretePoint { x: 1, y: 2 }.into();
+    let s: Pullable<Point> = clm::from_stream(futures::stream::iter(vec![p]), ctx);
+    let mut done = sink::tcp::json(s, &addr, FRAMED, Reconnect::NEVER, ctx);
+    assert!(matches!(block_on(done.pull(ctx)), Control::Failed(_)));
+}
+
+#[rewrite(main)]
+#[test]
+fn tcp() {
+    source_sink();
+    malformed_record();
+    unreachable_sink();
+}
@@ -0,0 +1,65 @@
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+#[rewrite(main)]
+#[test]
+fn arithmetic() {
+    let t: DateTime = DateTime::from_unix_timestamp(90);
+    let d: Duration = Duration::from_seconds(60);
+    assert_eq!(DateTime::unix_timestamp(DateTime::add(t, d)), 150);
+    assert_eq!(DateTime::truncate(t, d), DateTime::from_unix_timestamp(60));
+    // 400 years after the epoch, which is out of range for an i64 of nanoseconds.
+    let far: DateTime = DateTime::from_unix_timestamp(12614400090);
+    assert_eq!(
+        DateTime::truncate(far, d),
+        DateTime::from_unix_timestamp(12614400060)
+    );
+    let before: DateTime = DateTime::from_unix_timestamp(-90);
+    assert_eq!(
+        DateTime::truncate(before, d),
+        DateTime::from_unix_timestamp(-120)
+    );
+    assert!(DateTime::sub(t, d) < t);
+    assert_eq!(
+        Duration::whole_seconds(DateTime::since(t, DateTime::EPOCH)),
+        90
+    );
+    assert_eq!(Duration::whole_milliseconds(Duration::div(d, 4)), 15000);
+}
+
+#[rewrite(main)]
+#[test]
+fn parse_and_format() {
+    let f: String = String::from_str("[year]-[month]-[day] [hour]:[minute]:[second]");
+    let t: DateTime = Option::unwrap(DateTime::parse(String::from_str("2022-03-04 05:06:07"), f));
+    assert!(Option::is_none(DateTime::parse(
+        String::from_str("2022-03-04"),
+        f
+    )));
+    assert_eq!(DateTime::year(t), 2022);
+    assert_eq!(DateTime::month(t), 3);
+    assert_eq!(DateTime::second(t), 7);
+    assert!(String::eq(
+        DateTime::format(t, f),
+        String::from_str("2022-03-04 05:06:07")
+    ));
+}
+
+#[rewrite(main)]
+#[test]
+fn chars() {
+    let c: char = Option::unwrap(char_from_u32(97));
+    assert!(Option::is_none(char_from_u32(0xD800)));
+    assert!(char_is_alphabetic(c));
+    assert_eq!(char_to_ascii_uppercase(c), 'A');
+    assert_eq!(char_to_u32(c), 97);
+}
+
+#[rewrite(main)]
+#[test]
+fn conversion() {
+    let t: (DateTime, Duration, char) = (DateTime::EPOCH, Duration::from_seconds(1), 'x');
+    let u: (DateTime, Duration, char) = t.into_sendable(ctx).into_sharable(ctx);
+    assert_eq!(t, u);
+}
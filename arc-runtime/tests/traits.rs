@@ -0,0 +1,101 @@
+#![allow(unused)]
+
+mod monoid {
+    use arc_runtime::prelude::*;
+
+    #[rewrite]
+    pub struct Sum {
+        pub val: i32,
+    }
+
+    #[rewrite]
+    pub trait Monoid {
+        fn identity(ctx: Context) -> Self;
+        fn merge(self, other: Self, ctx: Context) -> Self;
+    }
+
+    #[rewrite]
+    impl Monoid for i32 {
+        fn identity(ctx: Context) -> i32 {
+            0
+        }
+
+        fn merge(self, other: i32, ctx: Context) -> i32 {
+            self + other
+        }
+    }
+
+    #[rewrite]
+    impl Monoid for Sum {
+        fn identity(ctx: Context) -> Sum {
+            new!(Sum { val: 0 })
+        }
+
+        fn merge(self, other: Sum, ctx: Context) -> Sum {
+            new!(Sum {
+                val: self.val + other.val
+            })
+        }
+    }
+
+    #[rewrite]
+    fn merge3<T: Monoid>(a: T, b: T, c: T) -> T {
+        Monoid::merge(Monoid::merge(a, b), Monoid::merge(c, T::identity()))
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        assert_eq!(Monoid::merge(1, 2), 3);
+        assert_eq!(call!(Monoid_merge(1, 2)), 3);
+        assert_eq!(call!(Monoid_merge::<i32>(1, 2)), 3);
+        assert_eq!(call!(Monoid_identity::<i32>()), 0);
+        assert_eq!(call!(merge3(1, 2, 3)), 6);
+        let x: Sum = new!(Sum { val: 1 });
+        let y: Sum = new!(Sum { val: 2 });
+        let z: Sum = call!(Monoid_merge(x, y));
+        assert_eq!(z.val, 3);
+        let z: Sum = call!(merge3(x, y, z));
+        assert_eq!(z.val, 6);
+        let z: Sum = call!(Monoid_identity::<Sum>());
+        assert_eq!(z.val, 0);
+    }
+}
+
+mod class {
+    use arc_runtime::prelude::*;
+
+    #[rewrite]
+    pub trait Add<T> {
+        fn add(self, other: T, ctx: Context) -> Self;
+    }
+
+    #[rewrite]
+    impl Add<i32> for Vec<i32> {
+        fn add(self, other: i32, ctx: Context) -> Vec<i32> {
+            Vec::from_iter(self.as_slice(ctx).iter().map(|x| x + other), ctx)
+        }
+    }
+
+    #[rewrite]
+    impl<T> Add<T> for Option<T> {
+        fn add(self, other: T, ctx: Context) -> Option<T> {
+            Option::some(Option::unwrap_or(self, other, ctx), ctx)
+        }
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn test() {
+        let v: Vec<i32> = vector![1, 2,];
+        let w: Vec<i32> = call!(Add_add(v, 1));
+        assert_eq!(Option::unwrap(Vec::get(w, 1)), 3);
+        let w: Vec<i32> = call!(Add_add::<i32, Vec<i32>>(w, 1));
+        assert_eq!(Option::unwrap(Vec::get(w, 0)), 3);
+        let a: Option<i32> = Option::none();
+        let a: Option<i32> = Add::add(a, 5);
+        assert_eq!(Option::unwrap_or(a, 0), 5);
+        let a: Option<i32> = call!(Add_add::<i32, Option<i32>>(a, 7));
+        assert_eq!(Option::unwrap_or(a, 0), 5);
+    }
+}
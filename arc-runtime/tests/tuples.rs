@@ -0,0 +1,31 @@
+#![allow(unused)]
+
+use arc_runtime::prelude::*;
+
+#[rewrite(main)]
+#[test]
+fn round_trip() {
+    let s: String = String::from_str("arc");
+    let v: Vec<i32> = vector![1, 2, 3,];
+    let t: ((String, Vec<i32>), (bool, (u8,))) = ((s, v), (true, (7,)));
+    let t: ((String, Vec<i32>), (bool, (u8,))) = t.into_sendable(ctx).into_sharable(ctx);
+    let a: (String, Vec<i32>) = t.0;
+    let b: (bool, (u8,)) = t.1;
+    assert!(String::eq(a.0, String::from_str("arc")));
+    assert_eq!(Vec::len(a.1), 3);
+    assert_eq!(Option::unwrap(Vec::get(a.1, 2)), 3);
+    assert!(b.0);
+    assert_eq!((b.1).0, 7);
+}
+
+fn serialize(t: (String, Vec<i32>), ctx: Context) -> std::string::String {
+    serde_json::to_string(&t.into_sendable(ctx)).unwrap()
+}
+
+#[rewrite(main)]
+#[test]
+fn serde() {
+    let s: String = String::from_str("arc");
+    let v: Vec<i32> = vector![1, 2,];
+    assert_eq!(serialize((s, v)).as_str(), r#"["arc",[1,2]]"#);
+}
@@ -0,0 +1,67 @@
+#![allow(unused)]
+
+#[allow(non_camel_case_types)]
+mod test_toplevel {
+
+    use arc_runtime::prelude::*;
+
+    declare_functions!(double, is_even, add, compare);
+
+    #[rewrite]
+    fn double(a: i32) -> i32 {
+        a + a
+    }
+
+    #[rewrite]
+    fn is_even(a: i32) -> bool {
+        a % 2 == 0
+    }
+
+    #[rewrite]
+    fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    #[rewrite]
+    fn compare(a: i32, b: i32) -> i32 {
+        b - a
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn higher_order() {
+        let v: Vec<i32> = vector![1, 2, 3, 4,];
+        let d: Vec<i32> = Vec::map(v, function!(double));
+        assert_eq!(Option::unwrap(Vec::get(d, 3)), 8);
+        let e: Vec<i32> = Vec::filter(v, function!(is_even));
+        assert_eq!(Vec::len(e), 2);
+        assert_eq!(Vec::fold(v, 0, function!(add)), 10);
+        assert!(Vec::any(v, function!(is_even)));
+        assert!(!Vec::all(v, function!(is_even)));
+        assert_eq!(Option::unwrap_or(Vec::find(v, function!(is_even)), 0), 2);
+        Vec::sort_by(v, function!(compare));
+        assert_eq!(Option::unwrap(Vec::get(v, 0)), 4);
+    }
+
+    #[rewrite(main)]
+    #[test]
+    fn combinators() {
+        let a: Vec<i32> = vector![1, 2, 3,];
+        let b: Vec<i32> = vector![4, 5,];
+        let c: Vec<i32> = Vec::concat(a, b);
+        assert_eq!(Vec::len(c), 5);
+        let s: Vec<i32> = Option::unwrap(Vec::slice(c, 1, 4));
+        assert!(Option::is_none(Vec::slice(c, 4, 6)));
+        assert_eq!(Option::unwrap(Vec::get(s, 0)), 2);
+        assert_eq!(Vec::len(s), 3);
+        let z: Vec<(i32, i32)> = Vec::zip(a, b);
+        assert_eq!(Option::unwrap(Vec::get(z, 1)), (2, 5));
+        assert_eq!(Vec::len(z), 2);
+        assert!(Vec::contains(c, 5));
+        assert_eq!(Result::unwrap_or(Vec::binary_search(c, 3), 0), 2);
+        let r: Result<usize, usize> = Vec::binary_search(c, 6);
+        assert_eq!(unwrap!(ResultErr, r), 5);
+        Vec::reverse(c);
+        assert_eq!(Option::unwrap(Vec::get(c, 0)), 5);
+    }
+}